@@ -1,12 +1,20 @@
-use serde::Serialize;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::env;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 use std::time::Duration;
 use std::time::{SystemTime, UNIX_EPOCH};
 use chrono::{DateTime, Utc};
 use sysinfo::System;
-use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, Position, Size, State};
+use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, Position, Size, State};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_opener::OpenerExt;
 
 #[derive(Clone, Serialize)]
 struct ProcessInfo {
@@ -40,6 +48,30 @@ struct SpotifyTrack {
     album: String,
     artwork_url: String,
     is_playing: bool,
+    position_ms: u64,
+    duration_ms: u64,
+    shuffle: bool,
+    // "off", "track" or "context"
+    repeat: String,
+    artwork_cached_url: Option<String>,
+    palette: Option<ArtworkPalette>,
+}
+
+#[derive(Clone, Serialize)]
+struct ArtworkPalette {
+    dominant: String,
+    accent: String,
+    text: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct PlayedTrack {
+    played_at_ms: u128,
+    track_name: String,
+    artist: String,
+    album: String,
+    listened_ms: u64,
+    duration_ms: u64,
 }
 
 #[derive(Clone, Serialize)]
@@ -68,22 +100,488 @@ struct JiraTicket {
     status: String,
     assignee: String,
     url: String,
+    priority: Option<String>,
+    priority_icon_url: Option<String>,
+    issue_type: Option<String>,
+    // Jira status category key: "new", "indeterminate" or "done"
+    status_category: String,
+    status_color: String,
+    sprint: Option<String>,
+    due_date: Option<String>,
+    labels: Vec<String>,
+    reporter: Option<String>,
+    updated: String,
+    extra_fields: HashMap<String, serde_json::Value>,
+    // Assigned to the user the Jira credentials belong to
+    is_mine: bool,
+}
+
+#[derive(Clone, Serialize)]
+struct JiraBoardColumn {
+    name: String,
+    tickets: Vec<JiraTicket>,
+}
+
+#[derive(Clone, Serialize)]
+struct JiraSprint {
+    id: u64,
+    name: String,
+    goal: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    columns: Vec<JiraBoardColumn>,
+    done_count: usize,
+    remaining_count: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct JiraTransition {
+    id: String,
+    name: String,
+    to_status: String,
+    to_status_category: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct JiraQuery {
+    id: String,
+    name: String,
+    jql: String,
+    // Send notifications when tickets appear in or change status within this query
+    #[serde(default)]
+    notify: bool,
+}
+
+#[derive(Clone, Serialize)]
+struct JiraTicketChange {
+    query_id: String,
+    query_name: String,
+    key: String,
+    summary: String,
+    url: String,
+    // "added" or "status_changed"
+    kind: String,
+    from_status: Option<String>,
+    to_status: String,
+}
+
+#[derive(Clone, Serialize)]
+struct JiraTicketList {
+    tickets: Vec<JiraTicket>,
+    // Number of issues matching the JQL, which may exceed `tickets.len()`
+    total: u64,
+    // Notifications about this query that could not be delivered
+    errors: Vec<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct ForgeItem {
+    // "github" or "gitlab"
+    provider: String,
+    repository: String,
+    number: u64,
+    title: String,
+    author: String,
+    url: String,
+    updated_at: String,
+    is_draft: bool,
+    // "success", "failure", "pending" or None when no pipeline ran
+    ci_status: Option<String>,
+}
+
+#[derive(Clone, Serialize, Default)]
+struct ForgeOverview {
+    review_requested: Vec<ForgeItem>,
+    authored: Vec<ForgeItem>,
+    assigned_issues: Vec<ForgeItem>,
+    // Per-provider failures, so one broken token does not hide the other provider
+    errors: Vec<String>,
+}
+
+#[derive(Clone, Deserialize)]
+struct PipelineWatch {
+    // "github" or "gitlab"
+    provider: String,
+    // "owner/repo" on GitHub, "group/project" on GitLab
+    repository: String,
+    #[serde(default = "default_pipeline_branch")]
+    branch: String,
+    // Defaults to notifying only for main and master
+    #[serde(default)]
+    notify: Option<bool>,
+}
+
+impl PipelineWatch {
+    fn notifies(&self) -> bool {
+        self.notify
+            .unwrap_or(matches!(self.branch.as_str(), "main" | "master"))
+    }
+}
+
+fn default_pipeline_branch() -> String {
+    "main".to_string()
+}
+
+#[derive(Clone, Serialize)]
+struct PipelineRun {
+    id: u64,
+    name: String,
+    // "success", "failure", "pending" or "neutral" (skipped, manual, ...)
+    status: String,
+    url: String,
+    commit_sha: String,
+    commit_message: Option<String>,
+    triggered_by: Option<String>,
+    started_at: Option<String>,
+    duration_secs: Option<i64>,
+    // Only filled in for the latest run when it failed
+    failed_jobs: Vec<String>,
+    // Why the jobs behind `failed_jobs` and the duration could not be loaded
+    jobs_error: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct PipelineStatus {
+    provider: String,
+    repository: String,
+    branch: String,
+    runs: Vec<PipelineRun>,
+    error: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct PipelineFailure {
+    provider: String,
+    repository: String,
+    branch: String,
+    url: String,
+    commit_message: Option<String>,
+    failed_jobs: Vec<String>,
+}
+
+#[derive(Clone, Serialize, Default)]
+struct GitRepoStatus {
+    name: String,
+    path: String,
+    // None when HEAD is detached or the branch has no commits yet
+    branch: Option<String>,
+    upstream: Option<String>,
+    ahead: usize,
+    behind: usize,
+    staged_files: usize,
+    modified_files: usize,
+    untracked_files: usize,
+    conflicted_files: usize,
+    stash_count: usize,
+    last_commit_time_ms: Option<i64>,
+    last_commit_summary: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Clone, Deserialize)]
+struct HealthCheckConfig {
+    name: String,
+    // A URL for HTTP checks, "host:port" or a URL for TCP and TLS, a hostname for DNS
+    #[serde(alias = "target")]
+    url: String,
+    // "http" (default), "tcp", "dns" or "tls"
+    #[serde(default = "default_health_check_kind")]
+    kind: String,
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+    // Certificates expiring within this many days mark a TLS check degraded
+    #[serde(default)]
+    tls_warn_days: Option<i64>,
+    #[serde(flatten)]
+    assertions: HealthAssertions,
+    #[serde(flatten)]
+    policy: HealthPolicy,
+    // HTTP method, GET when unset
+    #[serde(default)]
+    method: Option<String>,
+    // Plain, non-secret headers; secrets go through `auth`
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    auth: Option<HealthCheckAuth>,
+    // The secret behind `auth`, looked up once per round before the checks run
+    #[serde(skip)]
+    auth_secret: Option<Result<String, String>>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum HealthCheckAuth {
+    Bearer { token: SecretRef },
+    Basic { username: String, password: SecretRef },
+    Header { name: String, value: SecretRef },
+}
+
+impl HealthCheckAuth {
+    fn secret(&self) -> &SecretRef {
+        match self {
+            HealthCheckAuth::Bearer { token } => token,
+            HealthCheckAuth::Basic { password, .. } => password,
+            HealthCheckAuth::Header { value, .. } => value,
+        }
+    }
+}
+
+// Where a credential lives, so config never holds the secret itself:
+// {"env": "STATUS_TOKEN"} or {"keychain": {"service": "dashboard", "account": "status"}}
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SecretRef {
+    Env(String),
+    Keychain { service: String, account: String },
+}
+
+impl SecretRef {
+    // Keychain access blocks (and may prompt the user), so keep it off the async workers
+    async fn resolve(&self) -> Result<String, String> {
+        match self {
+            SecretRef::Env(name) => {
+                env::var(name).map_err(|_| format!("{} environment variable not set", name))
+            }
+            SecretRef::Keychain { service, account } => {
+                let (service, account) = (service.clone(), account.clone());
+                tokio::task::spawn_blocking(move || {
+                    keyring::Entry::new(&service, &account)
+                        .and_then(|entry| entry.get_password())
+                        .map_err(|e| {
+                            format!("Failed to read keychain entry {}/{}: {}", service, account, e)
+                        })
+                })
+                .await
+                .map_err(|e| format!("Keychain task failed: {}", e))?
+            }
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Default)]
+struct HealthPolicy {
+    // Immediate re-checks before a failed attempt counts, default 1
+    retries: Option<u32>,
+    // Down once `failure_threshold` of the last `failure_window` checks failed, default 2 of 3
+    failure_threshold: Option<usize>,
+    failure_window: Option<usize>,
+    // Flapping once the up/down state changed `flap_threshold` times within
+    // the last `flap_window` checks, default 4 of 10
+    flap_threshold: Option<usize>,
+    flap_window: Option<usize>,
+}
+
+impl HealthPolicy {
+    fn retries(&self) -> u32 {
+        self.retries.unwrap_or(1)
+    }
+
+    fn failure_threshold(&self) -> usize {
+        self.failure_threshold.unwrap_or(2).max(1)
+    }
+
+    fn failure_window(&self) -> usize {
+        self.failure_window.unwrap_or(3).max(self.failure_threshold())
+    }
+
+    fn flap_threshold(&self) -> usize {
+        self.flap_threshold.unwrap_or(4).max(2)
+    }
+
+    fn flap_window(&self) -> usize {
+        self.flap_window.unwrap_or(10).max(self.flap_threshold() + 1)
+    }
+}
+
+#[derive(Clone, Deserialize, Default)]
+struct HealthAssertions {
+    // Any 2xx status when empty
+    #[serde(default)]
+    expected_status: Vec<u16>,
+    body_contains: Option<String>,
+    body_regex: Option<String>,
+    // Dot-separated path ("checks.database.status") or JSON pointer ("/checks/0/status")
+    json_path: Option<String>,
+    json_equals: Option<serde_json::Value>,
+    // Header name to expected value, null only requires the header to be present
+    #[serde(default)]
+    required_headers: HashMap<String, Option<String>>,
+    // Slower responses mark the service degraded. Not u128, serde cannot read those
+    // through #[serde(flatten)]
+    max_latency_ms: Option<u64>,
+    // Report failed body, JSON and header assertions as degraded instead of down
+    #[serde(default)]
+    degraded_on_content_mismatch: bool,
+}
+
+fn default_health_check_kind() -> String {
+    "http".to_string()
+}
+
+impl HealthAssertions {
+    fn needs_body(&self) -> bool {
+        self.body_contains.is_some() || self.body_regex.is_some() || self.json_path.is_some()
+    }
 }
 
 #[derive(Clone, Serialize)]
 struct ServiceHealth {
     name: String,
     url: String,
+    kind: String,
+    // True for both "up" and "degraded"
     is_up: bool,
+    // "up", "degraded", "down" or "flapping"
+    state: String,
+    // Human readable descriptions of every failed assertion
+    failed_assertions: Vec<String>,
+    // Requests made for this result, including immediate retries
+    attempts: u32,
+    // Failed checks within the service's failure window
+    recent_failures: usize,
     status_code: Option<u16>,
     latency_ms: Option<u128>,
     checked_at_ms: u128,
     error: Option<String>,
+    // Probe specific summary, e.g. the resolved addresses of a DNS check
+    details: Option<String>,
+    certificate: Option<TlsCertificateInfo>,
+}
+
+impl ServiceHealth {
+    fn new(service: &HealthCheckConfig, checked_at_ms: u128) -> Self {
+        ServiceHealth {
+            name: service.name.clone(),
+            url: service.url.clone(),
+            kind: service.kind.clone(),
+            is_up: true,
+            state: "up".to_string(),
+            failed_assertions: Vec::new(),
+            attempts: 1,
+            recent_failures: 0,
+            status_code: None,
+            latency_ms: None,
+            checked_at_ms,
+            error: None,
+            details: None,
+            certificate: None,
+        }
+    }
+
+    fn apply_failures(&mut self, down: Vec<String>, degraded: Vec<String>) {
+        let state = if !down.is_empty() {
+            "down"
+        } else if !degraded.is_empty() {
+            "degraded"
+        } else {
+            "up"
+        };
+        self.is_up = state != "down";
+        self.state = state.to_string();
+        self.failed_assertions = down.into_iter().chain(degraded).collect();
+        self.error = self.failed_assertions.first().cloned();
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct HealthTransition {
+    name: String,
+    url: String,
+    // "down", "recovered" or "flapping"
+    kind: String,
+    from_state: String,
+    to_state: String,
+    error: Option<String>,
+    changed_at_ms: u128,
+}
+
+#[derive(Clone, Deserialize)]
+struct AlertWebhook {
+    url: String,
+    // "slack" for Slack-compatible {"text": ...} payloads, "generic" posts the transition as JSON
+    #[serde(default = "default_alert_webhook_format")]
+    format: String,
+    #[serde(default)]
+    respect_quiet_hours: bool,
+}
+
+fn default_alert_webhook_format() -> String {
+    "generic".to_string()
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct HealthSample {
+    name: String,
+    checked_at_ms: u128,
+    state: String,
+    latency_ms: Option<u128>,
+    error: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct OutageIncident {
+    started_at_ms: u128,
+    // None while the outage is still ongoing
+    ended_at_ms: Option<u128>,
+    duration_ms: u128,
+    error: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct UptimeBucket {
+    start_ms: u128,
+    end_ms: u128,
+    // None when there were no checks in this bucket
+    uptime_percent: Option<f64>,
+    // Worst state seen in the bucket: "up", "degraded" or "down"
+    state: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct ServiceUptime {
+    name: String,
+    uptime_24h: Option<f64>,
+    uptime_7d: Option<f64>,
+    uptime_30d: Option<f64>,
+    latency_p50_ms: Option<u128>,
+    latency_p95_ms: Option<u128>,
+    latency_p99_ms: Option<u128>,
+    buckets: Vec<UptimeBucket>,
+    incidents: Vec<OutageIncident>,
+}
+
+#[derive(Clone, Serialize)]
+struct TlsCertificateInfo {
+    subject: String,
+    issuer: String,
+    expires_at_ms: i64,
+    days_until_expiry: i64,
+}
+
+#[derive(Default)]
+struct ProbeOutcome {
+    details: Option<String>,
+    certificate: Option<TlsCertificateInfo>,
+    down: Vec<String>,
+    degraded: Vec<String>,
 }
 
 #[derive(Clone, Serialize)]
 struct SentryIssue {
+    id: String,
+    // Position of the source in SENTRY_SOURCES, issue ids are only unique per source
+    source_id: usize,
     title: String,
+    organization: String,
+    // Project slug, so merged lists can be grouped per project
+    project: String,
+    // "unresolved", "resolved" or "ignored"
+    status: String,
+    assigned_to: Option<String>,
+    is_bookmarked: bool,
     last_seen: String,
     first_seen: String,
     age: String,
@@ -93,6 +591,71 @@ struct SentryIssue {
     url: String,
 }
 
+#[derive(Clone, Serialize)]
+struct SentryIssueList {
+    issues: Vec<SentryIssue>,
+    // Sources that failed while the others still loaded
+    errors: Vec<String>,
+}
+
+#[derive(Clone, Deserialize)]
+struct SentrySource {
+    organization: String,
+    // "https://de.sentry.io" for EU orgs or a self-hosted URL, defaults to SENTRY_BASE_URL
+    #[serde(default = "default_sentry_base_url")]
+    base_url: String,
+    // Falls back to SENTRY_AUTH_TOKEN, useful when a self-hosted instance needs its own token
+    #[serde(default)]
+    auth_token: Option<SecretRef>,
+    // Project slugs or numeric ids, every project of the organization when empty
+    #[serde(default)]
+    projects: Vec<String>,
+    #[serde(default)]
+    environments: Vec<String>,
+    #[serde(default = "default_sentry_query")]
+    query: String,
+    #[serde(default = "default_sentry_sort")]
+    sort: String,
+    #[serde(default = "default_sentry_stats_period")]
+    stats_period: String,
+    #[serde(default = "default_sentry_limit")]
+    limit: usize,
+}
+
+fn default_sentry_base_url() -> String {
+    env::var("SENTRY_BASE_URL").unwrap_or_else(|_| "https://sentry.io".to_string())
+}
+
+impl SentrySource {
+    fn api_url(&self) -> String {
+        format!("{}/api/0", self.base_url.trim_end_matches('/'))
+    }
+
+    async fn token(&self) -> Result<String, String> {
+        match &self.auth_token {
+            Some(secret) => secret.resolve().await,
+            None => env::var("SENTRY_AUTH_TOKEN")
+                .map_err(|_| "SENTRY_AUTH_TOKEN environment variable not set".to_string()),
+        }
+    }
+}
+
+fn default_sentry_query() -> String {
+    "is:unresolved".to_string()
+}
+
+fn default_sentry_sort() -> String {
+    "date".to_string()
+}
+
+fn default_sentry_stats_period() -> String {
+    "90d".to_string()
+}
+
+fn default_sentry_limit() -> usize {
+    15
+}
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -292,7 +855,11 @@ fn collect_spotify_track() -> Result<SpotifyTrack, String> {
                     set albumName to album of current track
                     set artworkUrl to artwork url of current track
                     set playerState to player state as string
-                    return trackName & "|" & artistName & "|" & albumName & "|" & artworkUrl & "|" & playerState
+                    set trackPosition to player position as string
+                    set trackDuration to duration of current track as string
+                    set isShuffling to shuffling as string
+                    set isRepeating to repeating as string
+                    return trackName & "|" & artistName & "|" & albumName & "|" & artworkUrl & "|" & playerState & "|" & trackPosition & "|" & trackDuration & "|" & isShuffling & "|" & isRepeating
                 else
                     return "not_running"
                 end if
@@ -316,228 +883,3892 @@ fn collect_spotify_track() -> Result<SpotifyTrack, String> {
         }
 
         let parts: Vec<&str> = result.split('|').collect();
-        if parts.len() < 5 {
+        if parts.len() < 9 {
             return Err("Failed to parse Spotify data".to_string());
         }
 
+        // Player position is in seconds and may use a locale-specific decimal comma
+        let position_secs = parts[5].replace(',', ".").parse::<f64>().unwrap_or(0.0);
+
         Ok(SpotifyTrack {
             track_name: parts[0].to_string(),
             artist: parts[1].to_string(),
             album: parts[2].to_string(),
             artwork_url: parts[3].to_string(),
             is_playing: parts[4] == "playing",
+            position_ms: (position_secs * 1000.0) as u64,
+            duration_ms: parts[6].parse().unwrap_or(0),
+            shuffle: parts[7] == "true",
+            // AppleScript only exposes a flag, not the repeat mode
+            repeat: if parts[8] == "true" { "context" } else { "off" }.to_string(),
+            artwork_cached_url: None,
+            palette: None,
         })
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
     {
-        Err("Spotify integration is only supported on macOS".to_string())
-    }
-}
+        let output = Command::new("playerctl")
+            .args([
+                "--player=spotify",
+                "metadata",
+                "--format",
+                "{{title}}|{{artist}}|{{album}}|{{mpris:artUrl}}|{{status}}|{{position}}|{{mpris:length}}",
+            ])
+            .output()
+            .map_err(|e| format!("Failed to execute playerctl: {}", e))?;
 
-fn collect_cpu_usage() -> CpuUsage {
-    let mut sys = System::new_all();
+        if !output.status.success() {
+            return Err("Spotify is not running".to_string());
+        }
 
-    // Need to refresh twice with a delay to get accurate CPU usage
-    sys.refresh_cpu_all();
-    std::thread::sleep(std::time::Duration::from_millis(200));
-    sys.refresh_cpu_all();
+        let result = String::from_utf8_lossy(&output.stdout).trim().to_string();
 
-    // Get per-core usage
-    let cores: Vec<CpuCore> = sys
-        .cpus()
-        .iter()
-        .enumerate()
-        .map(|(index, cpu)| CpuCore {
-            core_id: index,
-            usage: cpu.cpu_usage(),
-        })
-        .collect();
+        let parts: Vec<&str> = result.split('|').collect();
+        if parts.len() < 7 {
+            return Err("Failed to parse Spotify data".to_string());
+        }
 
-    // Calculate overall CPU usage
-    let overall_usage = if !cores.is_empty() {
-        cores.iter().map(|c| c.usage).sum::<f32>() / cores.len() as f32
-    } else {
-        0.0
-    };
+        // Shuffle and loop status are not part of the metadata template
+        let playerctl_status = |command: &str| {
+            Command::new("playerctl")
+                .args(["--player=spotify", command])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+                .unwrap_or_default()
+        };
+        let repeat = match playerctl_status("loop").as_str() {
+            "Track" => "track",
+            "Playlist" => "context",
+            _ => "off",
+        };
 
-    // Get top CPU-consuming processes
-    let mut processes: Vec<_> = sys
-        .processes()
-        .iter()
-        .map(|(_, process)| CpuProcessInfo {
-            name: process.name().to_string_lossy().to_string(),
-            cpu_usage: process.cpu_usage(),
+        // MPRIS reports position and length in microseconds
+        Ok(SpotifyTrack {
+            track_name: parts[0].to_string(),
+            artist: parts[1].to_string(),
+            album: parts[2].to_string(),
+            artwork_url: parts[3].to_string(),
+            is_playing: parts[4] == "Playing",
+            position_ms: parts[5].parse::<u64>().unwrap_or(0) / 1000,
+            duration_ms: parts[6].parse::<u64>().unwrap_or(0) / 1000,
+            shuffle: playerctl_status("shuffle") == "On",
+            repeat: repeat.to_string(),
+            artwork_cached_url: None,
+            palette: None,
         })
-        .collect();
-
-    // Sort by CPU usage (descending) and take top 3
-    processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap());
-    let top_processes = processes.into_iter().take(3).collect();
+    }
 
-    CpuUsage {
-        overall_usage,
-        cores,
-        top_processes,
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        Err("Spotify integration is only supported on macOS and Linux".to_string())
     }
 }
 
-async fn collect_jira_tickets() -> Result<Vec<JiraTicket>, String> {
-    let api_token = env::var("JIRA_API_TOKEN")
-        .map_err(|_| "JIRA_API_TOKEN environment variable not set".to_string())?;
-    let email = env::var("JIRA_EMAIL")
-        .map_err(|_| "JIRA_EMAIL environment variable not set".to_string())?;
-    let base_url = env::var("JIRA_BASE_URL")
-        .unwrap_or_else(|_| "https://zw-systems.atlassian.net".to_string());
+#[derive(Clone, Copy)]
+enum SpotifyAction {
+    PlayPause,
+    Next,
+    Previous,
+    Seek(u64),
+    Volume(u8),
+}
+
+fn run_spotify_action(action: SpotifyAction) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let command = match action {
+            SpotifyAction::PlayPause => "playpause".to_string(),
+            SpotifyAction::Next => "next track".to_string(),
+            SpotifyAction::Previous => "previous track".to_string(),
+            SpotifyAction::Seek(position_ms) => {
+                format!("set player position to {}", position_ms as f64 / 1000.0)
+            }
+            SpotifyAction::Volume(volume) => format!("set sound volume to {}", volume),
+        };
+        let script = format!(
+            r#"
+            tell application "Spotify"
+                if it is running then
+                    {}
+                    return "ok"
+                else
+                    return "not_running"
+                end if
+            end tell
+        "#,
+            command
+        );
 
-    let raw_jql = env::var("JIRA_JQL").unwrap_or_default();
-    let jql = if raw_jql.trim().is_empty() {
-        "updated >= -3650d ORDER BY updated DESC".to_string()
-    } else {
-        raw_jql
-    };
+        let output = Command::new("osascript")
+            .arg("-e")
+            .arg(script)
+            .output()
+            .map_err(|e| format!("Failed to execute AppleScript: {}", e))?;
 
-    let client = reqwest::Client::new();
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
 
-    let auth_check = client
-        .get(format!("{}/rest/api/3/myself", base_url))
-        .basic_auth(&email, Some(&api_token))
-        .header("Accept", "application/json")
-        .send()
-        .await
-        .map_err(|e| format!("Failed to validate Jira credentials: {}", e))?;
+        if String::from_utf8_lossy(&output.stdout).trim() == "not_running" {
+            return Err("Spotify is not running".to_string());
+        }
 
-    if !auth_check.status().is_success() {
-        let status = auth_check.status();
-        let body = auth_check.text().await.unwrap_or_default();
-        return Err(format!(
-            "Jira authentication failed ({}). Check JIRA_EMAIL and JIRA_API_TOKEN. {}",
-            status, body
-        ));
+        Ok(())
     }
 
-    let url = format!(
-        "{}/rest/api/3/search/jql?jql={}&maxResults=15&fields=summary,status,assignee",
-        base_url,
-        urlencoding::encode(&jql)
+    #[cfg(target_os = "linux")]
+    {
+        // MPRIS positions are in seconds and volume is a 0.0-1.0 fraction
+        let args = match action {
+            SpotifyAction::PlayPause => vec!["play-pause".to_string()],
+            SpotifyAction::Next => vec!["next".to_string()],
+            SpotifyAction::Previous => vec!["previous".to_string()],
+            SpotifyAction::Seek(position_ms) => vec![
+                "position".to_string(),
+                format!("{}", position_ms as f64 / 1000.0),
+            ],
+            SpotifyAction::Volume(volume) => {
+                vec!["volume".to_string(), format!("{}", volume as f64 / 100.0)]
+            }
+        };
+
+        let output = Command::new("playerctl")
+            .arg("--player=spotify")
+            .args(&args)
+            .output()
+            .map_err(|e| format!("Failed to execute playerctl: {}", e))?;
+
+        if !output.status.success() {
+            return Err("Spotify is not running".to_string());
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        let _ = action;
+        Err("Spotify integration is only supported on macOS and Linux".to_string())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SpotifyBackend {
+    Local,
+    WebApi,
+}
+
+fn spotify_backend() -> SpotifyBackend {
+    match env::var("SPOTIFY_BACKEND")
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+        .as_str()
+    {
+        "web" | "webapi" | "web_api" => SpotifyBackend::WebApi,
+        _ => SpotifyBackend::Local,
+    }
+}
+
+const SPOTIFY_KEYRING_SERVICE: &str = "dashboard";
+const SPOTIFY_KEYRING_USER: &str = "spotify_refresh_token";
+const SPOTIFY_WEB_SCOPES: &str =
+    "user-read-currently-playing user-read-playback-state user-modify-playback-state";
+
+struct SpotifyWebConfig {
+    client_id: String,
+    redirect_port: u16,
+    accounts_base_url: String,
+    api_base_url: String,
+}
+
+impl SpotifyWebConfig {
+    fn from_env() -> Result<Self, String> {
+        let client_id = env::var("SPOTIFY_CLIENT_ID")
+            .map_err(|_| "SPOTIFY_CLIENT_ID environment variable not set".to_string())?;
+        let redirect_port = env::var("SPOTIFY_REDIRECT_PORT")
+            .ok()
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(8898);
+        // Tests build the config directly to point both base URLs at a mock server
+        Ok(Self {
+            client_id,
+            redirect_port,
+            accounts_base_url: "https://accounts.spotify.com".to_string(),
+            api_base_url: "https://api.spotify.com".to_string(),
+        })
+    }
+
+    fn redirect_uri(&self) -> String {
+        format!("http://127.0.0.1:{}/callback", self.redirect_port)
+    }
+}
+
+#[derive(Default)]
+struct SpotifyWebSession {
+    access_token: Option<String>,
+    expires_at: Option<std::time::Instant>,
+}
+
+struct SpotifyWebClient {
+    client: reqwest::Client,
+    session: tokio::sync::Mutex<SpotifyWebSession>,
+}
+
+fn spotify_keyring_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(SPOTIFY_KEYRING_SERVICE, SPOTIFY_KEYRING_USER)
+        .map_err(|e| format!("Failed to open keychain entry: {}", e))
+}
+
+// Keychain access blocks (and may prompt the user), so keep it off the async workers
+async fn load_spotify_refresh_token() -> Result<String, String> {
+    tokio::task::spawn_blocking(|| spotify_keyring_entry()?.get_password().map_err(|e| e.to_string()))
+        .await
+        .map_err(|e| format!("Keychain task failed: {}", e))?
+        .map_err(|_| {
+            "Spotify Web API is not authorized yet. Log in to Spotify from the dashboard."
+                .to_string()
+        })
+}
+
+async fn store_spotify_refresh_token(refresh_token: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        spotify_keyring_entry()?
+            .set_password(&refresh_token)
+            .map_err(|e| format!("Failed to store Spotify refresh token: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Keychain task failed: {}", e))?
+}
+
+fn random_token(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+fn generate_pkce_pair() -> (String, String) {
+    let verifier = random_token(64);
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+    (verifier, challenge)
+}
+
+fn parse_query_params(path: &str) -> HashMap<String, String> {
+    let query = path.split_once('?').map(|(_, query)| query).unwrap_or("");
+    query
+        .split('&')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            let value = urlencoding::decode(value)
+                .map(|decoded| decoded.into_owned())
+                .unwrap_or_else(|_| value.to_string());
+            Some((key.to_string(), value))
+        })
+        .collect()
+}
+
+async fn wait_for_spotify_callback(
+    listener: &tokio::net::TcpListener,
+    expected_state: &str,
+) -> Result<String, String> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    loop {
+        let (mut stream, _) = listener
+            .accept()
+            .await
+            .map_err(|e| format!("Failed to accept Spotify callback: {}", e))?;
+
+        let mut buffer = [0u8; 4096];
+        let read = stream
+            .read(&mut buffer)
+            .await
+            .map_err(|e| format!("Failed to read Spotify callback: {}", e))?;
+        let request = String::from_utf8_lossy(&buffer[..read]);
+
+        // Request line looks like "GET /callback?code=...&state=... HTTP/1.1"
+        let path = request
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .unwrap_or("");
+
+        if !path.starts_with("/callback") {
+            // Browsers also ask for /favicon.ico, ignore anything else
+            let _ = stream
+                .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .await;
+            continue;
+        }
+
+        let params = parse_query_params(path);
+        let result = if let Some(error) = params.get("error") {
+            Err(format!("Spotify login was denied: {}", error))
+        } else if params.get("state").map(String::as_str) != Some(expected_state) {
+            Err("Spotify login failed: state mismatch".to_string())
+        } else {
+            params
+                .get("code")
+                .cloned()
+                .ok_or_else(|| "Spotify login failed: missing authorization code".to_string())
+        };
+
+        let body = if result.is_ok() {
+            "Spotify login complete. You can close this window."
+        } else {
+            "Spotify login failed. You can close this window."
+        };
+        let _ = stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .as_bytes(),
+            )
+            .await;
+
+        return result;
+    }
+}
+
+// Stores the access token in the session and returns it together with the
+// refresh token, which Spotify may rotate on every exchange
+fn apply_spotify_token_response(
+    session: &mut SpotifyWebSession,
+    json: &serde_json::Value,
+) -> Result<(String, Option<String>), String> {
+    let access_token = json["access_token"]
+        .as_str()
+        .ok_or("Invalid Spotify token response: missing 'access_token'")?
+        .to_string();
+    let expires_in = json["expires_in"].as_u64().unwrap_or(3600);
+
+    session.access_token = Some(access_token.clone());
+    session.expires_at = Some(
+        std::time::Instant::now() + Duration::from_secs(expires_in.saturating_sub(60)),
     );
 
-    let response = client
-        .get(&url)
-        .basic_auth(&email, Some(&api_token))
+    Ok((access_token, json["refresh_token"].as_str().map(str::to_string)))
+}
+
+async fn exchange_spotify_code(
+    web: &SpotifyWebClient,
+    config: &SpotifyWebConfig,
+    code: &str,
+    verifier: &str,
+) -> Result<(String, Option<String>), String> {
+    let redirect_uri = config.redirect_uri();
+    let json = request_spotify_token(
+        web,
+        config,
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri.as_str()),
+            ("client_id", config.client_id.as_str()),
+            ("code_verifier", verifier),
+        ],
+    )
+    .await?;
+
+    let mut session = web.session.lock().await;
+    apply_spotify_token_response(&mut session, &json)
+}
+
+async fn refresh_spotify_token(
+    web: &SpotifyWebClient,
+    config: &SpotifyWebConfig,
+    session: &mut SpotifyWebSession,
+    refresh_token: &str,
+) -> Result<(String, Option<String>), String> {
+    let json = request_spotify_token(
+        web,
+        config,
+        &[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", config.client_id.as_str()),
+        ],
+    )
+    .await?;
+
+    apply_spotify_token_response(session, &json)
+}
+
+async fn request_spotify_token(
+    web: &SpotifyWebClient,
+    config: &SpotifyWebConfig,
+    form: &[(&str, &str)],
+) -> Result<serde_json::Value, String> {
+    let response = web
+        .client
+        .post(format!("{}/api/token", config.accounts_base_url))
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to request Spotify token: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Spotify token request failed ({}): {}", status, body));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Spotify token response: {}", e))
+}
+
+async fn spotify_web_access_token(
+    web: &SpotifyWebClient,
+    config: &SpotifyWebConfig,
+) -> Result<String, String> {
+    let mut session = web.session.lock().await;
+
+    if let (Some(token), Some(expires_at)) = (&session.access_token, session.expires_at) {
+        if std::time::Instant::now() < expires_at {
+            return Ok(token.clone());
+        }
+    }
+
+    let refresh_token = load_spotify_refresh_token().await?;
+    let (access_token, rotated) =
+        refresh_spotify_token(web, config, &mut session, &refresh_token).await?;
+
+    if let Some(rotated) = rotated.filter(|rotated| *rotated != refresh_token) {
+        store_spotify_refresh_token(rotated).await?;
+    }
+
+    Ok(access_token)
+}
+
+async fn collect_spotify_web_track(
+    web: &SpotifyWebClient,
+    config: &SpotifyWebConfig,
+) -> Result<SpotifyTrack, String> {
+    let token = spotify_web_access_token(web, config).await?;
+
+    let response = web
+        .client
+        .get(format!("{}/v1/me/player", config.api_base_url))
+        .bearer_auth(&token)
         .header("Accept", "application/json")
         .send()
         .await
-        .map_err(|e| format!("Failed to fetch Jira tickets: {}", e))?;
+        .map_err(|e| format!("Failed to fetch Spotify playback: {}", e))?;
+
+    if response.status() == reqwest::StatusCode::NO_CONTENT {
+        return Err("No track playing".to_string());
+    }
+
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        // Force a refresh on the next poll
+        web.session.lock().await.access_token = None;
+        return Err("Spotify access token expired".to_string());
+    }
 
     if !response.status().is_success() {
         let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Jira API error ({}): {}", status, error_text));
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Spotify API error ({}): {}", status, body));
     }
 
     let json: serde_json::Value = response
         .json()
         .await
-        .map_err(|e| format!("Failed to parse Jira response: {}", e))?;
+        .map_err(|e| format!("Failed to parse Spotify response: {}", e))?;
+
+    // Ads and podcast episodes come back without a track item
+    let item = &json["item"];
+    if item.is_null() {
+        return Err("No track playing".to_string());
+    }
 
-    let issues = json["issues"]
+    let artist = item["artists"]
         .as_array()
-        .ok_or("Invalid Jira response format: missing 'issues' array")?;
+        .map(|artists| {
+            artists
+                .iter()
+                .filter_map(|artist| artist["name"].as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default();
 
-    if issues.is_empty() {
-        return Err(format!(
-            "Jira returned 0 tickets for JQL: `{}`. Verify query and Jira permissions.",
-            jql
-        ));
+    Ok(SpotifyTrack {
+        track_name: item["name"].as_str().unwrap_or("").to_string(),
+        artist,
+        album: item["album"]["name"].as_str().unwrap_or("").to_string(),
+        artwork_url: item["album"]["images"][0]["url"]
+            .as_str()
+            .unwrap_or("")
+            .to_string(),
+        is_playing: json["is_playing"].as_bool().unwrap_or(false),
+        position_ms: json["progress_ms"].as_u64().unwrap_or(0),
+        duration_ms: item["duration_ms"].as_u64().unwrap_or(0),
+        shuffle: json["shuffle_state"].as_bool().unwrap_or(false),
+        repeat: json["repeat_state"].as_str().unwrap_or("off").to_string(),
+        artwork_cached_url: None,
+        palette: None,
+    })
+}
+
+async fn run_spotify_web_action(
+    web: &SpotifyWebClient,
+    config: &SpotifyWebConfig,
+    action: SpotifyAction,
+) -> Result<(), String> {
+    let player_url = format!("{}/v1/me/player", config.api_base_url);
+
+    let request = match action {
+        SpotifyAction::PlayPause => {
+            let is_playing = collect_spotify_web_track(web, config)
+                .await
+                .map(|track| track.is_playing)
+                .unwrap_or(false);
+            if is_playing {
+                web.client.put(format!("{}/pause", player_url))
+            } else {
+                web.client.put(format!("{}/play", player_url))
+            }
+        }
+        SpotifyAction::Next => web.client.post(format!("{}/next", player_url)),
+        SpotifyAction::Previous => web.client.post(format!("{}/previous", player_url)),
+        SpotifyAction::Seek(position_ms) => web
+            .client
+            .put(format!("{}/seek?position_ms={}", player_url, position_ms)),
+        SpotifyAction::Volume(volume) => web
+            .client
+            .put(format!("{}/volume?volume_percent={}", player_url, volume)),
+    };
+
+    let token = spotify_web_access_token(web, config).await?;
+    let response = request
+        .bearer_auth(&token)
+        .body("")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send Spotify command: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Spotify API error ({}): {}", status, body));
     }
 
-    let tickets: Vec<JiraTicket> = issues
+    Ok(())
+}
+
+const ARTWORK_CACHE_MAX_BYTES: u64 = 50 * 1024 * 1024;
+const ARTWORK_PALETTE_LIMIT: usize = 256;
+
+struct ArtworkCache {
+    dir: OnceLock<PathBuf>,
+    client: reqwest::Client,
+    palettes: Mutex<HashMap<String, ArtworkPalette>>,
+}
+
+fn artwork_cache_key(artwork_url: &str) -> String {
+    Sha256::digest(artwork_url.as_bytes())
         .iter()
-        .map(|issue| {
-            let key = issue["key"].as_str().unwrap_or("").to_string();
-            let summary = issue["fields"]["summary"].as_str().unwrap_or("").to_string();
-            let status = issue["fields"]["status"]["name"]
-                .as_str()
-                .unwrap_or("Unknown")
-                .to_string();
-            
-            // Handle assignee which can be null
-            let assignee = if issue["fields"]["assignee"].is_null() {
-                "Unassigned".to_string()
-            } else {
-                issue["fields"]["assignee"]["displayName"]
-                    .as_str()
-                    .unwrap_or("Unassigned")
-                    .to_string()
-            };
-            
-            let url = format!("{}/browse/{}", base_url, key);
+        .take(16)
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn artwork_scheme_url(key: &str) -> String {
+    // Windows webviews expose custom schemes as http://<scheme>.localhost
+    if cfg!(windows) {
+        format!("http://artwork.localhost/{}", key)
+    } else {
+        format!("artwork://localhost/{}", key)
+    }
+}
+
+fn artwork_mime_type(bytes: &[u8]) -> &'static str {
+    match image::guess_format(bytes) {
+        Ok(image::ImageFormat::Png) => "image/png",
+        _ => "image/jpeg",
+    }
+}
+
+fn color_to_hex(color: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+fn color_saturation(color: [u8; 3]) -> f64 {
+    let max = *color.iter().max().unwrap_or(&0) as f64;
+    let min = *color.iter().min().unwrap_or(&0) as f64;
+    if max == 0.0 {
+        0.0
+    } else {
+        (max - min) / max
+    }
+}
+
+fn color_distance(a: [u8; 3], b: [u8; 3]) -> f64 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| (*x as f64 - *y as f64).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+fn extract_artwork_palette(bytes: &[u8]) -> Option<ArtworkPalette> {
+    let image = image::load_from_memory(bytes).ok()?.thumbnail(48, 48).to_rgb8();
+
+    // Bucket pixels into a 16x16x16 colour cube and average each bucket
+    let mut buckets: HashMap<(u8, u8, u8), [u64; 4]> = HashMap::new();
+    for pixel in image.pixels() {
+        let [r, g, b] = pixel.0;
+        let bucket = buckets.entry((r >> 4, g >> 4, b >> 4)).or_default();
+        bucket[0] += r as u64;
+        bucket[1] += g as u64;
+        bucket[2] += b as u64;
+        bucket[3] += 1;
+    }
+
+    let mut colors: Vec<([u8; 3], u64)> = buckets
+        .values()
+        .map(|[r, g, b, count]| {
+            (
+                [(r / count) as u8, (g / count) as u8, (b / count) as u8],
+                *count,
+            )
+        })
+        .collect();
+    colors.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+    let dominant = colors.first()?.0;
+
+    // Prefer a vivid colour that still covers a fair share of the artwork
+    let accent = colors
+        .iter()
+        .filter(|(color, _)| color_distance(*color, dominant) > 80.0)
+        .map(|(color, count)| (*color, color_saturation(*color) * (*count as f64).sqrt()))
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(color, _)| color)
+        .unwrap_or(dominant);
+
+    let luminance =
+        (0.2126 * dominant[0] as f64 + 0.7152 * dominant[1] as f64 + 0.0722 * dominant[2] as f64)
+            / 255.0;
+    let text = if luminance > 0.6 { "#000000" } else { "#ffffff" };
+
+    Some(ArtworkPalette {
+        dominant: color_to_hex(dominant),
+        accent: color_to_hex(accent),
+        text: text.to_string(),
+    })
+}
+
+impl ArtworkCache {
+    fn path_for(&self, key: &str) -> Option<PathBuf> {
+        // Keys are hex digests, anything else could escape the cache directory
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.dir.get().map(|dir| dir.join(key))
+    }
+
+    fn read(&self, key: &str) -> Option<Vec<u8>> {
+        std::fs::read(self.path_for(key)?).ok()
+    }
+
+    async fn palette(&self, key: &str, path: &Path) -> Option<ArtworkPalette> {
+        if let Some(palette) = self.palettes.lock().ok()?.get(key) {
+            return Some(palette.clone());
+        }
+
+        let bytes = tokio::fs::read(path).await.ok()?;
+        let palette = tokio::task::spawn_blocking(move || extract_artwork_palette(&bytes))
+            .await
+            .ok()??;
+
+        let mut palettes = self.palettes.lock().ok()?;
+        if palettes.len() >= ARTWORK_PALETTE_LIMIT {
+            palettes.clear();
+        }
+        palettes.insert(key.to_string(), palette.clone());
+        Some(palette)
+    }
+
+    async fn enrich(&self, spotify: &mut Result<SpotifyTrack, String>) {
+        let Ok(track) = spotify else {
+            return;
+        };
+        if track.artwork_url.is_empty() {
+            return;
+        }
+
+        let key = artwork_cache_key(&track.artwork_url);
+        let Some(path) = self.path_for(&key) else {
+            return;
+        };
+
+        // Cached artwork is never re-fetched, so it keeps working offline. A failed
+        // download leaves artwork_cached_url empty and the widget uses artwork_url
+        if !tokio::fs::try_exists(&path).await.unwrap_or(false)
+            && self.download(&track.artwork_url, &path).await.is_err()
+        {
+            return;
+        }
+
+        track.artwork_cached_url = Some(artwork_scheme_url(&key));
+        track.palette = self.palette(&key, &path).await;
+    }
+
+    async fn download(&self, url: &str, path: &Path) -> Result<(), String> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to download artwork: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Artwork download failed ({})", response.status()));
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| format!("Failed to read artwork: {}", e))?;
+
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            write_file_atomically(&path, &bytes)
+                .map_err(|e| format!("Failed to cache artwork: {}", e))?;
+            if let Some(dir) = path.parent() {
+                evict_artwork_cache(dir, ARTWORK_CACHE_MAX_BYTES);
+            }
+            Ok(())
+        })
+        .await
+        .map_err(|e| format!("Failed to cache artwork: {}", e))?
+    }
+}
+
+// Deletes the least recently written artwork until the cache fits in `max_bytes`
+fn evict_artwork_cache(dir: &Path, max_bytes: u64) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut files: Vec<(PathBuf, u64, std::time::SystemTime)> = entries
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() {
+                return None;
+            }
+            let modified = metadata.modified().unwrap_or(std::time::UNIX_EPOCH);
+            Some((entry.path(), metadata.len(), modified))
+        })
+        .collect();
+
+    let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+    files.sort_by_key(|(_, _, modified)| *modified);
+
+    for (path, size, _) in files {
+        if total <= max_bytes {
+            break;
+        }
+        if std::fs::remove_file(&path).is_ok() {
+            total -= size;
+        }
+    }
+}
+
+// Writes to a temporary sibling and renames it over `path`, so a crash
+// never leaves a truncated file behind
+fn write_file_atomically(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp = path.with_file_name(format!(".{}.{}.tmp", file_name, random_token(8)));
+
+    std::fs::write(&tmp, contents)?;
+    std::fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp);
+    })
+}
+
+fn record_spotify_play(
+    history: &Arc<Mutex<SpotifyHistory>>,
+    spotify: &Result<SpotifyTrack, String>,
+) {
+    let pending = history.lock().ok().and_then(|mut history| {
+        if history.observe(spotify) {
+            history.pending_save()
+        } else {
+            None
+        }
+    });
+
+    if let Some((path, contents, generation)) = pending {
+        let history = history.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            save_spotify_history(&history, &path, &contents, generation)
+        });
+    }
+}
+
+// Saves run one at a time and a snapshot older than the one already on disk is
+// skipped, so a slow write can neither overwrite newer history nor its error
+fn save_spotify_history(
+    history: &Mutex<SpotifyHistory>,
+    path: &Path,
+    contents: &str,
+    generation: u64,
+) {
+    let Some(written) = history.lock().ok().map(|h| h.written_generation.clone()) else {
+        return;
+    };
+    let Ok(mut written) = written.lock() else {
+        return;
+    };
+    if *written >= generation {
+        return;
+    }
+
+    let result = write_file_atomically(path, contents.as_bytes());
+    *written = generation;
+    if let Ok(mut history) = history.lock() {
+        history.save_error = result
+            .err()
+            .map(|err| format!("Failed to save Spotify history: {}", err));
+    }
+}
+
+fn unix_time_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+const SPOTIFY_HISTORY_LIMIT: usize = 500;
+// A play stays open across collect errors and pauses until this much time
+// passed without the track playing
+const SPOTIFY_HISTORY_IDLE_LIMIT: Duration = Duration::from_secs(10 * 60);
+
+struct CurrentPlay {
+    track: PlayedTrack,
+    is_playing: bool,
+    last_seen: std::time::Instant,
+    last_active: std::time::Instant,
+    last_position_ms: u64,
+}
+
+#[derive(Default)]
+struct SpotifyHistory {
+    entries: VecDeque<PlayedTrack>,
+    current: Option<CurrentPlay>,
+    path: Option<PathBuf>,
+    // Failure of the last save, cleared by the next successful one
+    save_error: Option<String>,
+    // Bumped for every snapshot handed out by `pending_save`
+    save_generation: u64,
+    // Generation last written to disk, held while writing so saves run in order
+    written_generation: Arc<Mutex<u64>>,
+}
+
+impl SpotifyHistory {
+    fn load(path: PathBuf) -> Self {
+        let entries = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            entries,
+            current: None,
+            path: Some(path),
+            save_error: None,
+            save_generation: 0,
+            written_generation: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    // Returns true when a finished play was added to the history
+    fn observe(&mut self, spotify: &Result<SpotifyTrack, String>) -> bool {
+        let now = std::time::Instant::now();
+
+        if let Some(current) = self.current.as_mut() {
+            // Only count time while playing, and cap the gap so a sleeping
+            // machine does not inflate the listened duration
+            if current.is_playing {
+                let elapsed = now.duration_since(current.last_seen).min(Duration::from_secs(10));
+                current.track.listened_ms += elapsed.as_millis() as u64;
+            }
+            current.last_seen = now;
+        }
+
+        let is_idle = self
+            .current
+            .as_ref()
+            .is_some_and(|current| now.duration_since(current.last_active) > SPOTIFY_HISTORY_IDLE_LIMIT);
+
+        let track = match spotify {
+            Ok(track) => track,
+            // A single failed poll must not split a play in two
+            Err(_) => return is_idle && self.finish_current(),
+        };
+
+        let is_same_track = self.current.as_ref().is_some_and(|current| {
+            current.track.track_name == track.track_name
+                && current.track.artist == track.artist
+                && current.track.album == track.album
+        });
+        // Jumping from well into the track back to its start means it is played again
+        let is_replay = is_same_track
+            && self.current.as_ref().is_some_and(|current| {
+                current.last_position_ms >= 30_000 && track.position_ms < 10_000
+            });
+
+        if is_same_track && !is_replay && (!is_idle || track.is_playing) {
+            if let Some(current) = self.current.as_mut() {
+                current.is_playing = track.is_playing;
+                current.last_position_ms = track.position_ms;
+                if track.is_playing {
+                    current.last_active = now;
+                }
+            }
+            return false;
+        }
+
+        let finished = self.finish_current();
+        if track.is_playing {
+            self.current = Some(CurrentPlay {
+                track: PlayedTrack {
+                    played_at_ms: unix_time_ms(),
+                    track_name: track.track_name.clone(),
+                    artist: track.artist.clone(),
+                    album: track.album.clone(),
+                    listened_ms: 0,
+                    duration_ms: track.duration_ms,
+                },
+                is_playing: true,
+                last_seen: now,
+                last_active: now,
+                last_position_ms: track.position_ms,
+            });
+        }
+        finished
+    }
+
+    fn finish_current(&mut self) -> bool {
+        let Some(current) = self.current.take() else {
+            return false;
+        };
+
+        // Skip tracks that were merely skipped past
+        if current.track.listened_ms < 5000 {
+            return false;
+        }
+
+        self.entries.push_back(current.track);
+        while self.entries.len() > SPOTIFY_HISTORY_LIMIT {
+            self.entries.pop_front();
+        }
+        true
+    }
+
+    // Path and serialized entries, written by the caller outside the lock
+    fn pending_save(&mut self) -> Option<(PathBuf, String, u64)> {
+        let path = self.path.clone()?;
+        let contents = serde_json::to_string(&self.entries).ok()?;
+        self.save_generation += 1;
+        Some((path, contents, self.save_generation))
+    }
+
+    fn recent(&self, limit: usize) -> Vec<PlayedTrack> {
+        self.entries.iter().rev().take(limit).cloned().collect()
+    }
+}
+
+fn collect_cpu_usage() -> CpuUsage {
+    let mut sys = System::new_all();
+
+    // Need to refresh twice with a delay to get accurate CPU usage
+    sys.refresh_cpu_all();
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    sys.refresh_cpu_all();
+
+    // Get per-core usage
+    let cores: Vec<CpuCore> = sys
+        .cpus()
+        .iter()
+        .enumerate()
+        .map(|(index, cpu)| CpuCore {
+            core_id: index,
+            usage: cpu.cpu_usage(),
+        })
+        .collect();
+
+    // Calculate overall CPU usage
+    let overall_usage = if !cores.is_empty() {
+        cores.iter().map(|c| c.usage).sum::<f32>() / cores.len() as f32
+    } else {
+        0.0
+    };
+
+    // Get top CPU-consuming processes
+    let mut processes: Vec<_> = sys
+        .processes()
+        .iter()
+        .map(|(_, process)| CpuProcessInfo {
+            name: process.name().to_string_lossy().to_string(),
+            cpu_usage: process.cpu_usage(),
+        })
+        .collect();
+
+    // Sort by CPU usage (descending) and take top 3
+    processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap());
+    let top_processes = processes.into_iter().take(3).collect();
+
+    CpuUsage {
+        overall_usage,
+        cores,
+        top_processes,
+    }
+}
+
+const DEFAULT_JIRA_QUERY_ID: &str = "default";
+
+fn jira_queries() -> Result<Vec<JiraQuery>, String> {
+    // JIRA_QUERIES holds a JSON array of {"id", "name", "jql"} objects,
+    // otherwise fall back to the single JIRA_JQL query
+    if let Ok(raw) = env::var("JIRA_QUERIES") {
+        if !raw.trim().is_empty() {
+            let queries: Vec<JiraQuery> = serde_json::from_str(&raw)
+                .map_err(|e| format!("Failed to parse JIRA_QUERIES: {}", e))?;
+            if queries.is_empty() {
+                return Err("JIRA_QUERIES does not contain any queries".to_string());
+            }
+            return Ok(queries);
+        }
+    }
+
+    let raw_jql = env::var("JIRA_JQL").unwrap_or_default();
+    let jql = if raw_jql.trim().is_empty() {
+        "updated >= -3650d ORDER BY updated DESC".to_string()
+    } else {
+        raw_jql
+    };
+
+    let notify = env::var("JIRA_NOTIFY")
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+
+    Ok(vec![JiraQuery {
+        id: DEFAULT_JIRA_QUERY_ID.to_string(),
+        name: "Jira Tickets".to_string(),
+        jql,
+        notify,
+    }])
+}
+
+struct JiraFieldConfig {
+    sprint_field: String,
+    // (name, field id) pairs, e.g. ("story_points", "customfield_10016")
+    extra_fields: Vec<(String, String)>,
+}
+
+impl JiraFieldConfig {
+    fn from_env() -> Self {
+        let sprint_field = env::var("JIRA_SPRINT_FIELD")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| "customfield_10020".to_string());
+
+        // JIRA_EXTRA_FIELDS is a comma separated list of `field` or `name=field`
+        let extra_fields = env::var("JIRA_EXTRA_FIELDS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| match entry.split_once('=') {
+                Some((name, field)) => (name.trim().to_string(), field.trim().to_string()),
+                None => (entry.to_string(), entry.to_string()),
+            })
+            .collect();
+
+        Self {
+            sprint_field,
+            extra_fields,
+        }
+    }
+
+    fn search_fields(&self) -> String {
+        let mut fields = vec![
+            "summary",
+            "status",
+            "assignee",
+            "priority",
+            "issuetype",
+            "duedate",
+            "labels",
+            "reporter",
+            "updated",
+            self.sprint_field.as_str(),
+        ];
+        fields.extend(self.extra_fields.iter().map(|(_, field)| field.as_str()));
+        fields.join(",")
+    }
+}
+
+// Reads `key=value` from a legacy sprint string. Sprint names may contain commas,
+// so a value runs until the next ",key=" token rather than the next comma
+fn legacy_sprint_attribute(raw: &str, key: &str) -> Option<String> {
+    let body = raw.split_once('[')?.1;
+    let body = body.strip_suffix(']').unwrap_or(body);
+    let prefix = format!("{}=", key);
+
+    let start = if body.starts_with(&prefix) {
+        prefix.len()
+    } else {
+        body.find(&format!(",{}", prefix))? + 1 + prefix.len()
+    };
+    let value = &body[start..];
+
+    let end = value
+        .match_indices(',')
+        .map(|(index, _)| index)
+        .find(|&index| {
+            value[index + 1..].split_once('=').is_some_and(|(next_key, _)| {
+                !next_key.is_empty() && next_key.chars().all(|c| c.is_ascii_alphanumeric())
+            })
+        })
+        .unwrap_or(value.len());
+    Some(value[..end].to_string())
+}
+
+fn parse_jira_issue(
+    issue: &serde_json::Value,
+    base_url: &str,
+    field_config: &JiraFieldConfig,
+    current_account_id: Option<&str>,
+) -> JiraTicket {
+    let fields = &issue["fields"];
+    let key = issue["key"].as_str().unwrap_or("").to_string();
+    let summary = fields["summary"].as_str().unwrap_or("").to_string();
+    let status = fields["status"]["name"]
+        .as_str()
+        .unwrap_or("Unknown")
+        .to_string();
+
+    // Handle assignee which can be null
+    let assignee = if fields["assignee"].is_null() {
+        "Unassigned".to_string()
+    } else {
+        fields["assignee"]["displayName"]
+            .as_str()
+            .unwrap_or("Unassigned")
+            .to_string()
+    };
+
+    // The sprint field lists every sprint the issue was part of, prefer the active one.
+    // Older Server versions serialize each sprint as a string like
+    // "com.atlassian.greenhopper.service.sprint.Sprint@1f[id=3,state=ACTIVE,name=Sprint 7,...]"
+    let sprints: Vec<(String, String)> = fields[field_config.sprint_field.as_str()]
+        .as_array()
+        .map(|sprints| {
+            sprints
+                .iter()
+                .filter_map(|sprint| match sprint.as_str() {
+                    Some(raw) => Some((
+                        legacy_sprint_attribute(raw, "name")?,
+                        legacy_sprint_attribute(raw, "state").unwrap_or_default(),
+                    )),
+                    None => Some((
+                        sprint["name"].as_str()?.to_string(),
+                        sprint["state"].as_str().unwrap_or("").to_string(),
+                    )),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let sprint = sprints
+        .iter()
+        .find(|(_, state)| state.eq_ignore_ascii_case("active"))
+        .or_else(|| sprints.last())
+        .map(|(name, _)| name.clone());
+
+    let labels = fields["labels"]
+        .as_array()
+        .map(|labels| {
+            labels
+                .iter()
+                .filter_map(|label| label.as_str().map(|label| label.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let extra_fields = field_config
+        .extra_fields
+        .iter()
+        .map(|(name, field)| (name.clone(), fields[field.as_str()].clone()))
+        .collect();
+
+    let assignee_id = fields["assignee"]["accountId"]
+        .as_str()
+        .or_else(|| fields["assignee"]["name"].as_str());
+    let is_mine = current_account_id.is_some() && assignee_id == current_account_id;
+
+    let url = format!("{}/browse/{}", base_url, key);
+
+    JiraTicket {
+        key,
+        summary,
+        status,
+        assignee,
+        url,
+        priority: fields["priority"]["name"].as_str().map(|v| v.to_string()),
+        priority_icon_url: fields["priority"]["iconUrl"].as_str().map(|v| v.to_string()),
+        issue_type: fields["issuetype"]["name"].as_str().map(|v| v.to_string()),
+        status_category: fields["status"]["statusCategory"]["key"]
+            .as_str()
+            .unwrap_or("undefined")
+            .to_string(),
+        status_color: fields["status"]["statusCategory"]["colorName"]
+            .as_str()
+            .unwrap_or("")
+            .to_string(),
+        sprint,
+        due_date: fields["duedate"].as_str().map(|v| v.to_string()),
+        labels,
+        reporter: fields["reporter"]["displayName"].as_str().map(|v| v.to_string()),
+        updated: fields["updated"].as_str().unwrap_or("").to_string(),
+        extra_fields,
+        is_mine,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum JiraFlavor {
+    // Atlassian Cloud: basic auth with email + API token, REST API v3
+    Cloud,
+    // Self-hosted Data Center / Server: bearer personal access token, REST API v2
+    DataCenter,
+}
+
+struct JiraConfig {
+    flavor: JiraFlavor,
+    base_url: String,
+    email: String,
+    api_token: String,
+}
+
+impl JiraConfig {
+    fn from_env() -> Result<Self, String> {
+        let flavor = match env::var("JIRA_FLAVOR")
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase()
+            .as_str()
+        {
+            "datacenter" | "data_center" | "server" => JiraFlavor::DataCenter,
+            _ => JiraFlavor::Cloud,
+        };
+
+        let api_token = env::var("JIRA_API_TOKEN")
+            .map_err(|_| "JIRA_API_TOKEN environment variable not set".to_string())?;
+        // Personal access tokens identify the user on their own
+        let email = match flavor {
+            JiraFlavor::Cloud => env::var("JIRA_EMAIL")
+                .map_err(|_| "JIRA_EMAIL environment variable not set".to_string())?,
+            JiraFlavor::DataCenter => String::new(),
+        };
+        let base_url = env::var("JIRA_BASE_URL")
+            .unwrap_or_else(|_| "https://zw-systems.atlassian.net".to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        Ok(Self {
+            flavor,
+            base_url,
+            email,
+            api_token,
+        })
+    }
+
+    fn api_url(&self) -> String {
+        match self.flavor {
+            JiraFlavor::Cloud => format!("{}/rest/api/3", self.base_url),
+            JiraFlavor::DataCenter => format!("{}/rest/api/2", self.base_url),
+        }
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let request = match self.flavor {
+            JiraFlavor::Cloud => request.basic_auth(&self.email, Some(&self.api_token)),
+            JiraFlavor::DataCenter => request.bearer_auth(&self.api_token),
+        };
+        request.header("Accept", "application/json")
+    }
+}
+
+#[derive(Clone)]
+struct JiraUser {
+    // Cloud account id, or the username on Data Center
+    account_id: String,
+    display_name: String,
+}
+
+const JIRA_MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+fn parse_retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let value = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+
+    // Retry-After is either a number of seconds or an HTTP date
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let retry_at = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    retry_at.signed_duration_since(Utc::now()).to_std().ok()
+}
+
+// Shared by the pollers and commands so credentials are only validated once
+// instead of calling /myself before every request
+struct JiraClient {
+    client: reqwest::Client,
+    current_user: tokio::sync::Mutex<Option<JiraUser>>,
+}
+
+impl JiraClient {
+    fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(20))
+                .build()
+                .unwrap_or_else(|_| reqwest::Client::new()),
+            current_user: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    async fn current_user(&self, config: &JiraConfig) -> Result<JiraUser, String> {
+        let mut current_user = self.current_user.lock().await;
+        if let Some(user) = current_user.as_ref() {
+            return Ok(user.clone());
+        }
+
+        let response = self
+            .send_with_rate_limit(config, &|client: &reqwest::Client| {
+                client.get(format!("{}/myself", config.api_url()))
+            })
+            .await
+            .map_err(|e| format!("Failed to validate Jira credentials: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let hint = match config.flavor {
+                JiraFlavor::Cloud => "Check JIRA_EMAIL and JIRA_API_TOKEN",
+                JiraFlavor::DataCenter => "Check the personal access token in JIRA_API_TOKEN",
+            };
+            return Err(format!(
+                "Jira authentication failed ({}). {}. {}",
+                status, hint, body
+            ));
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Jira response: {}", e))?;
+
+        let account_id = match config.flavor {
+            JiraFlavor::Cloud => json["accountId"].as_str(),
+            JiraFlavor::DataCenter => json["name"].as_str(),
+        };
+        let user = JiraUser {
+            account_id: account_id
+                .ok_or("Invalid Jira response format: missing user id")?
+                .to_string(),
+            display_name: json["displayName"].as_str().unwrap_or("").to_string(),
+        };
+        *current_user = Some(user.clone());
+
+        Ok(user)
+    }
+
+    async fn send_with_rate_limit(
+        &self,
+        config: &JiraConfig,
+        build: &impl Fn(&reqwest::Client) -> reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, String> {
+        let mut attempt = 0;
+        loop {
+            let response = config
+                .authorize(build(&self.client))
+                .send()
+                .await
+                .map_err(|e| format!("Failed to reach Jira: {}", e))?;
+
+            if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS
+                || attempt >= JIRA_MAX_RATE_LIMIT_RETRIES
+            {
+                return Ok(response);
+            }
+
+            let delay = parse_retry_after(&response)
+                .unwrap_or(Duration::from_secs(5))
+                .min(Duration::from_secs(60));
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    async fn send(
+        &self,
+        config: &JiraConfig,
+        build: impl Fn(&reqwest::Client) -> reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, String> {
+        self.current_user(config).await?;
+
+        let mut response = self.send_with_rate_limit(config, &build).await?;
+
+        // The token may have been revoked or rotated, validate again once
+        // before reporting the failure
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            *self.current_user.lock().await = None;
+            self.current_user(config).await?;
+            response = self.send_with_rate_limit(config, &build).await?;
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(format!("Jira API error ({}): {}", status, error_text));
+        }
+
+        Ok(response)
+    }
+}
+
+async fn search_jira_cloud(
+    jira: &JiraClient,
+    config: &JiraConfig,
+    jql: &str,
+    search_fields: &str,
+    page_size: usize,
+    limit: usize,
+) -> Result<(Vec<serde_json::Value>, u64), String> {
+    let mut issues: Vec<serde_json::Value> = Vec::new();
+    let mut next_page_token: Option<String> = None;
+    let mut is_last = false;
+
+    while issues.len() < limit {
+        let mut url = format!(
+            "{}/search/jql?jql={}&maxResults={}&fields={}",
+            config.api_url(),
+            urlencoding::encode(jql),
+            page_size.min(limit - issues.len()),
+            urlencoding::encode(search_fields)
+        );
+        if let Some(token) = &next_page_token {
+            url.push_str(&format!("&nextPageToken={}", urlencoding::encode(token)));
+        }
+
+        let json: serde_json::Value = jira
+            .send(config, |client| client.get(&url))
+            .await?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Jira response: {}", e))?;
+
+        let page = json["issues"]
+            .as_array()
+            .ok_or("Invalid Jira response format: missing 'issues' array")?;
+        issues.extend(page.iter().cloned());
+
+        next_page_token = json["nextPageToken"].as_str().map(|token| token.to_string());
+        is_last = json["isLast"].as_bool().unwrap_or(next_page_token.is_none());
+        if is_last || page.is_empty() || next_page_token.is_none() {
+            break;
+        }
+    }
+    issues.truncate(limit);
+
+    // The token-based search does not report a total, so ask for an
+    // approximate count unless every match is already in hand
+    let total = if is_last {
+        issues.len() as u64
+    } else {
+        match jira
+            .send(config, |client| {
+                client
+                    .post(format!("{}/search/approximate-count", config.api_url()))
+                    .json(&serde_json::json!({ "jql": jql }))
+            })
+            .await
+        {
+            Ok(response) => response
+                .json::<serde_json::Value>()
+                .await
+                .ok()
+                .and_then(|json| json["count"].as_u64())
+                .unwrap_or(issues.len() as u64),
+            Err(_) => issues.len() as u64,
+        }
+    };
+
+    Ok((issues, total))
+}
+
+async fn search_jira_data_center(
+    jira: &JiraClient,
+    config: &JiraConfig,
+    jql: &str,
+    search_fields: &str,
+    page_size: usize,
+    limit: usize,
+) -> Result<(Vec<serde_json::Value>, u64), String> {
+    let mut issues: Vec<serde_json::Value> = Vec::new();
+    let mut total = 0;
+
+    // Server search is offset based and reports the total on every page
+    while issues.len() < limit {
+        let url = format!(
+            "{}/search?jql={}&startAt={}&maxResults={}&fields={}",
+            config.api_url(),
+            urlencoding::encode(jql),
+            issues.len(),
+            page_size.min(limit - issues.len()),
+            urlencoding::encode(search_fields)
+        );
+
+        let json: serde_json::Value = jira
+            .send(config, |client| client.get(&url))
+            .await?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Jira response: {}", e))?;
+
+        let page = json["issues"]
+            .as_array()
+            .ok_or("Invalid Jira response format: missing 'issues' array")?;
+        issues.extend(page.iter().cloned());
+
+        total = json["total"].as_u64().unwrap_or(issues.len() as u64);
+        if page.is_empty() || issues.len() as u64 >= total {
+            break;
+        }
+    }
+    issues.truncate(limit);
+
+    Ok((issues, total))
+}
+
+async fn collect_jira_tickets(jira: &JiraClient, jql: &str) -> Result<JiraTicketList, String> {
+    let config = JiraConfig::from_env()?;
+    let base_url = config.base_url.clone();
+
+    // The search endpoint caps a single page at 100 issues
+    let page_size = env::var("JIRA_PAGE_SIZE")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(50)
+        .clamp(1, 100);
+    let limit = env::var("JIRA_MAX_RESULTS")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(15)
+        .max(1);
+
+    let field_config = JiraFieldConfig::from_env();
+    let search_fields = field_config.search_fields();
+
+    let current_user = jira.current_user(&config).await?;
+
+    let (issues, total) = match config.flavor {
+        JiraFlavor::Cloud => {
+            search_jira_cloud(jira, &config, jql, &search_fields, page_size, limit).await?
+        }
+        JiraFlavor::DataCenter => {
+            search_jira_data_center(jira, &config, jql, &search_fields, page_size, limit).await?
+        }
+    };
+
+    if issues.is_empty() {
+        return Err(format!(
+            "Jira returned 0 tickets for JQL: `{}`. Verify query and Jira permissions.",
+            jql
+        ));
+    }
+
+    let tickets: Vec<JiraTicket> = issues
+        .iter()
+        .map(|issue| {
+            parse_jira_issue(issue, &base_url, &field_config, Some(&current_user.account_id))
+        })
+        .collect();
+
+    Ok(JiraTicketList {
+        tickets,
+        total,
+        errors: Vec::new(),
+    })
+}
+
+async fn collect_jira_sprint(jira: &JiraClient) -> Result<JiraSprint, String> {
+    let board_id = env::var("JIRA_BOARD_ID")
+        .map_err(|_| "JIRA_BOARD_ID environment variable not set".to_string())?;
+    let config = JiraConfig::from_env()?;
+    let field_config = JiraFieldConfig::from_env();
+    let current_user = jira.current_user(&config).await?;
+
+    let agile_url = format!("{}/rest/agile/1.0", config.base_url);
+    let board_id = urlencoding::encode(board_id.trim()).into_owned();
+
+    let sprints: serde_json::Value = jira
+        .send(&config, |client| {
+            client.get(format!("{}/board/{}/sprint?state=active", agile_url, board_id))
+        })
+        .await?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Jira sprint response: {}", e))?;
+
+    let sprint = sprints["values"]
+        .as_array()
+        .and_then(|values| values.first())
+        .ok_or_else(|| format!("No active sprint on Jira board {}", board_id))?;
+    let sprint_id = sprint["id"]
+        .as_u64()
+        .ok_or("Invalid Jira sprint response: missing 'id'")?;
+
+    let board_config: serde_json::Value = jira
+        .send(&config, |client| {
+            client.get(format!("{}/board/{}/configuration", agile_url, board_id))
+        })
+        .await?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Jira board configuration: {}", e))?;
+
+    // Board columns map to one or more workflow statuses by id
+    let mut columns: Vec<JiraBoardColumn> = Vec::new();
+    let mut column_by_status: HashMap<String, usize> = HashMap::new();
+    for column in board_config["columnConfig"]["columns"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+    {
+        let index = columns.len();
+        columns.push(JiraBoardColumn {
+            name: column["name"].as_str().unwrap_or("").to_string(),
+            tickets: Vec::new(),
+        });
+        for status in column["statuses"].as_array().cloned().unwrap_or_default() {
+            if let Some(status_id) = status["id"].as_str() {
+                column_by_status.insert(status_id.to_string(), index);
+            }
+        }
+    }
+
+    let search_fields = field_config.search_fields();
+    let mut issues: Vec<serde_json::Value> = Vec::new();
+    loop {
+        let url = format!(
+            "{}/sprint/{}/issue?startAt={}&maxResults=100&fields={}",
+            agile_url,
+            sprint_id,
+            issues.len(),
+            urlencoding::encode(&search_fields)
+        );
+        let page: serde_json::Value = jira
+            .send(&config, |client| client.get(&url))
+            .await?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Jira sprint issues: {}", e))?;
+
+        let page_issues = page["issues"]
+            .as_array()
+            .ok_or("Invalid Jira response format: missing 'issues' array")?;
+        issues.extend(page_issues.iter().cloned());
+
+        let total = page["total"].as_u64().unwrap_or(0) as usize;
+        if page_issues.is_empty() || issues.len() >= total {
+            break;
+        }
+    }
+
+    let mut done_count = 0;
+    let mut remaining_count = 0;
+    let mut unmapped: Vec<JiraTicket> = Vec::new();
+    for issue in &issues {
+        let ticket = parse_jira_issue(
+            issue,
+            &config.base_url,
+            &field_config,
+            Some(&current_user.account_id),
+        );
+        if ticket.status_category == "done" {
+            done_count += 1;
+        } else {
+            remaining_count += 1;
+        }
+
+        match issue["fields"]["status"]["id"]
+            .as_str()
+            .and_then(|status_id| column_by_status.get(status_id))
+        {
+            Some(index) => columns[*index].tickets.push(ticket),
+            None => unmapped.push(ticket),
+        }
+    }
+
+    if !unmapped.is_empty() {
+        columns.push(JiraBoardColumn {
+            name: "Other".to_string(),
+            tickets: unmapped,
+        });
+    }
+
+    Ok(JiraSprint {
+        id: sprint_id,
+        name: sprint["name"].as_str().unwrap_or("").to_string(),
+        goal: sprint["goal"]
+            .as_str()
+            .filter(|goal| !goal.is_empty())
+            .map(|goal| goal.to_string()),
+        start_date: sprint["startDate"].as_str().map(|v| v.to_string()),
+        end_date: sprint["endDate"].as_str().map(|v| v.to_string()),
+        columns,
+        done_count,
+        remaining_count,
+    })
+}
+
+async fn fetch_jira_transitions(
+    jira: &JiraClient,
+    issue_key: &str,
+) -> Result<Vec<JiraTransition>, String> {
+    let config = JiraConfig::from_env()?;
+
+    let json: serde_json::Value = jira
+        .send(&config, |client| {
+            client.get(format!(
+                "{}/issue/{}/transitions",
+                config.api_url(),
+                urlencoding::encode(issue_key)
+            ))
+        })
+        .await?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Jira response: {}", e))?;
+
+    let transitions = json["transitions"]
+        .as_array()
+        .ok_or("Invalid Jira response format: missing 'transitions' array")?;
+
+    Ok(transitions
+        .iter()
+        .map(|transition| JiraTransition {
+            id: transition["id"].as_str().unwrap_or("").to_string(),
+            name: transition["name"].as_str().unwrap_or("").to_string(),
+            to_status: transition["to"]["name"].as_str().unwrap_or("").to_string(),
+            to_status_category: transition["to"]["statusCategory"]["key"]
+                .as_str()
+                .unwrap_or("undefined")
+                .to_string(),
+        })
+        .collect())
+}
+
+async fn perform_jira_transition(
+    jira: &JiraClient,
+    issue_key: &str,
+    transition_id: &str,
+) -> Result<(), String> {
+    let config = JiraConfig::from_env()?;
+
+    jira.send(&config, |client| {
+        client
+            .post(format!(
+                "{}/issue/{}/transitions",
+                config.api_url(),
+                urlencoding::encode(issue_key)
+            ))
+            .json(&serde_json::json!({ "transition": { "id": transition_id } }))
+    })
+    .await?;
+
+    Ok(())
+}
+
+async fn perform_jira_assign(
+    jira: &JiraClient,
+    issue_key: &str,
+    account_id: &str,
+) -> Result<(), String> {
+    let config = JiraConfig::from_env()?;
+
+    let body = match config.flavor {
+        JiraFlavor::Cloud => serde_json::json!({ "accountId": account_id }),
+        JiraFlavor::DataCenter => serde_json::json!({ "name": account_id }),
+    };
+
+    jira.send(&config, |client| {
+        client
+            .put(format!(
+                "{}/issue/{}/assignee",
+                config.api_url(),
+                urlencoding::encode(issue_key)
+            ))
+            .json(&body)
+    })
+    .await?;
+
+    Ok(())
+}
+
+async fn perform_jira_comment(jira: &JiraClient, issue_key: &str, body: &str) -> Result<(), String> {
+    let config = JiraConfig::from_env()?;
+
+    // API v3 expects comments in Atlassian Document Format, v2 takes plain text
+    let document = match config.flavor {
+        JiraFlavor::Cloud => serde_json::json!({
+            "body": {
+                "type": "doc",
+                "version": 1,
+                "content": [{
+                    "type": "paragraph",
+                    "content": [{ "type": "text", "text": body }]
+                }]
+            }
+        }),
+        JiraFlavor::DataCenter => serde_json::json!({ "body": body }),
+    };
+
+    jira.send(&config, |client| {
+        client
+            .post(format!(
+                "{}/issue/{}/comment",
+                config.api_url(),
+                urlencoding::encode(issue_key)
+            ))
+            .json(&document)
+    })
+    .await?;
+
+    Ok(())
+}
+
+// `seen` holds the keys of the results before `previous`. Only the first page is
+// fetched, so a ticket missing from `previous` may just have been pushed off it
+fn diff_jira_tickets(
+    query: &JiraQuery,
+    previous: &JiraTicketList,
+    current: &JiraTicketList,
+    seen: &HashSet<String>,
+) -> Vec<JiraTicketChange> {
+    let previous_status: HashMap<&str, &str> = previous
+        .tickets
+        .iter()
+        .map(|ticket| (ticket.key.as_str(), ticket.status.as_str()))
+        .collect();
+
+    current
+        .tickets
+        .iter()
+        .filter_map(|ticket| {
+            let (kind, from_status) = match previous_status.get(ticket.key.as_str()) {
+                None if seen.contains(&ticket.key) => return None,
+                None => ("added", None),
+                Some(status) if *status != ticket.status => {
+                    ("status_changed", Some(status.to_string()))
+                }
+                Some(_) => return None,
+            };
+
+            Some(JiraTicketChange {
+                query_id: query.id.clone(),
+                query_name: query.name.clone(),
+                key: ticket.key.clone(),
+                summary: ticket.summary.clone(),
+                url: ticket.url.clone(),
+                kind: kind.to_string(),
+                from_status,
+                to_status: ticket.status.clone(),
+            })
+        })
+        .collect()
+}
+
+// Returns delivery failures so the poller can attach them to the query's result
+fn notify_jira_changes(app: &AppHandle, changes: &[JiraTicketChange]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for change in changes {
+        let title = match change.kind.as_str() {
+            "added" => format!("New in {}: {}", change.query_name, change.key),
+            _ => format!("{} moved to {}", change.key, change.to_status),
+        };
+
+        if let Err(err) = app
+            .notification()
+            .builder()
+            .title(title)
+            .body(&change.summary)
+            .show()
+        {
+            errors.push(format!("Failed to show Jira notification: {}", err));
+        }
+    }
+
+    if let Err(err) = app.emit("jira-ticket-changes", changes.to_vec()) {
+        errors.push(format!("Failed to emit Jira changes: {}", err));
+    }
+    errors
+}
+
+// Applies `patch` to every copy of the ticket across all queries and returns
+// the previous copies so the change can be rolled back
+fn patch_jira_ticket(
+    snapshot: &RwLock<AppSnapshot>,
+    issue_key: &str,
+    patch: impl Fn(&mut JiraTicket),
+) -> Vec<(String, JiraTicket)> {
+    let mut previous = Vec::new();
+
+    if let Ok(mut state) = snapshot.write() {
+        for (query_id, result) in state.jira.iter_mut() {
+            if let Ok(list) = result {
+                for ticket in list.tickets.iter_mut().filter(|t| t.key == issue_key) {
+                    previous.push((query_id.clone(), ticket.clone()));
+                    patch(ticket);
+                }
+            }
+        }
+    }
+
+    previous
+}
+
+// Rolls back a failed optimistic patch. Copies that no longer hold the
+// optimistic value were refreshed by the poller in the meantime and are kept
+fn restore_jira_tickets(
+    snapshot: &RwLock<AppSnapshot>,
+    previous: Vec<(String, JiraTicket)>,
+    is_patched: impl Fn(&JiraTicket) -> bool,
+) {
+    if let Ok(mut state) = snapshot.write() {
+        for (query_id, ticket) in previous {
+            if let Some(Ok(list)) = state.jira.get_mut(&query_id) {
+                if let Some(current) = list
+                    .tickets
+                    .iter_mut()
+                    .find(|t| t.key == ticket.key && is_patched(t))
+                {
+                    *current = ticket;
+                }
+            }
+        }
+    }
+}
+
+const FORGE_PAGE_SIZE: usize = 20;
+
+const GITHUB_OVERVIEW_QUERY: &str = r#"
+query($reviewQuery: String!, $authoredQuery: String!, $issueQuery: String!, $first: Int!) {
+  reviewRequested: search(query: $reviewQuery, type: ISSUE, first: $first) { nodes { ...pullRequest } }
+  authored: search(query: $authoredQuery, type: ISSUE, first: $first) { nodes { ...pullRequest } }
+  assigned: search(query: $issueQuery, type: ISSUE, first: $first) {
+    nodes {
+      ... on Issue { number title url updatedAt author { login } repository { nameWithOwner } }
+    }
+  }
+}
+fragment pullRequest on PullRequest {
+  number title url updatedAt isDraft
+  author { login }
+  repository { nameWithOwner }
+  commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+}
+"#;
+
+fn github_api_url() -> String {
+    env::var("GITHUB_API_URL")
+        .unwrap_or_else(|_| "https://api.github.com".to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+fn gitlab_api_url() -> String {
+    let base_url = env::var("GITLAB_BASE_URL")
+        .unwrap_or_else(|_| "https://gitlab.com".to_string());
+    format!("{}/api/v4", base_url.trim_end_matches('/'))
+}
+
+fn normalize_ci_status(status: &str) -> Option<String> {
+    match status.to_lowercase().as_str() {
+        "success" | "passed" => Some("success".to_string()),
+        // GitLab spells it "canceled", GitHub "cancelled"
+        "failure" | "failed" | "error" | "canceled" | "cancelled" | "timed_out"
+        | "startup_failure" => Some("failure".to_string()),
+        "pending" | "expected" | "running" | "created" | "preparing"
+        | "waiting_for_resource" | "scheduled" => Some("pending".to_string()),
+        _ => None,
+    }
+}
+
+fn parse_github_item(node: &serde_json::Value) -> Option<ForgeItem> {
+    // Search results may contain nodes that do not match the fragment
+    let number = node["number"].as_u64()?;
+
+    Some(ForgeItem {
+        provider: "github".to_string(),
+        repository: node["repository"]["nameWithOwner"]
+            .as_str()
+            .unwrap_or("")
+            .to_string(),
+        number,
+        title: node["title"].as_str().unwrap_or("").to_string(),
+        author: node["author"]["login"].as_str().unwrap_or("ghost").to_string(),
+        url: node["url"].as_str().unwrap_or("").to_string(),
+        updated_at: node["updatedAt"].as_str().unwrap_or("").to_string(),
+        is_draft: node["isDraft"].as_bool().unwrap_or(false),
+        ci_status: node["commits"]["nodes"][0]["commit"]["statusCheckRollup"]["state"]
+            .as_str()
+            .and_then(normalize_ci_status),
+    })
+}
+
+async fn collect_github_overview(overview: &mut ForgeOverview) -> Result<(), String> {
+    let Ok(token) = env::var("GITHUB_TOKEN") else {
+        return Ok(());
+    };
+    // GitHub Enterprise serves REST under /api/v3 and GraphQL under /api/graphql
+    let api_url = github_api_url();
+    let graphql_url = match api_url.strip_suffix("/api/v3") {
+        Some(host) => format!("{}/api/graphql", host),
+        None => format!("{}/graphql", api_url),
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+
+    let response = client
+        .post(&graphql_url)
+        .bearer_auth(&token)
+        .header("Accept", "application/json")
+        .header("User-Agent", "dashboard")
+        .json(&serde_json::json!({
+            "query": GITHUB_OVERVIEW_QUERY,
+            "variables": {
+                "reviewQuery": "is:pr is:open archived:false review-requested:@me",
+                "authoredQuery": "is:pr is:open archived:false author:@me",
+                "issueQuery": "is:issue is:open archived:false assignee:@me",
+                "first": FORGE_PAGE_SIZE,
+            }
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch GitHub data: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("GitHub API error ({}): {}", status, body));
+    }
+
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse GitHub response: {}", e))?;
+
+    parse_github_overview(&json, overview)
+}
+
+fn parse_github_overview(
+    json: &serde_json::Value,
+    overview: &mut ForgeOverview,
+) -> Result<(), String> {
+    if let Some(message) = json["errors"][0]["message"].as_str() {
+        return Err(format!("GitHub API error: {}", message));
+    }
+
+    let items = |alias: &str| -> Vec<ForgeItem> {
+        json["data"][alias]["nodes"]
+            .as_array()
+            .map(|nodes| nodes.iter().filter_map(parse_github_item).collect())
+            .unwrap_or_default()
+    };
+
+    overview.review_requested.extend(items("reviewRequested"));
+    overview.authored.extend(items("authored"));
+    overview.assigned_issues.extend(items("assigned"));
+
+    Ok(())
+}
+
+fn parse_gitlab_item(item: &serde_json::Value, reference_separator: char) -> ForgeItem {
+    // references.full looks like "group/project!12" or "group/project#34"
+    let repository = item["references"]["full"]
+        .as_str()
+        .and_then(|reference| reference.rsplit_once(reference_separator))
+        .map(|(repository, _)| repository.to_string())
+        .unwrap_or_default();
+
+    ForgeItem {
+        provider: "gitlab".to_string(),
+        repository,
+        number: item["iid"].as_u64().unwrap_or(0),
+        title: item["title"].as_str().unwrap_or("").to_string(),
+        author: item["author"]["username"].as_str().unwrap_or("").to_string(),
+        url: item["web_url"].as_str().unwrap_or("").to_string(),
+        updated_at: item["updated_at"].as_str().unwrap_or("").to_string(),
+        is_draft: item["draft"]
+            .as_bool()
+            .or_else(|| item["work_in_progress"].as_bool())
+            .unwrap_or(false),
+        ci_status: None,
+    }
+}
+
+// Pipelines are listed newest first
+fn latest_gitlab_pipeline_status(pipelines: &serde_json::Value) -> Option<String> {
+    pipelines[0]["status"].as_str().and_then(normalize_ci_status)
+}
+
+async fn fetch_gitlab_json(
+    client: &reqwest::Client,
+    token: &str,
+    url: &str,
+) -> Result<serde_json::Value, String> {
+    let response = client
+        .get(url)
+        .header("PRIVATE-TOKEN", token)
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch GitLab data: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("GitLab API error ({}): {}", status, body));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse GitLab response: {}", e))
+}
+
+async fn collect_gitlab_overview(overview: &mut ForgeOverview) -> Result<(), String> {
+    let Ok(token) = env::var("GITLAB_TOKEN") else {
+        return Ok(());
+    };
+    let api_url = gitlab_api_url();
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+
+    let user = fetch_gitlab_json(&client, &token, &format!("{}/user", api_url)).await?;
+    let username = user["username"]
+        .as_str()
+        .ok_or("Invalid GitLab response format: missing 'username'")?;
+
+    let review_requested = fetch_gitlab_json(
+        &client,
+        &token,
+        &format!(
+            "{}/merge_requests?state=opened&scope=all&reviewer_username={}&per_page={}",
+            api_url,
+            urlencoding::encode(username),
+            FORGE_PAGE_SIZE
+        ),
+    )
+    .await?;
+    overview.review_requested.extend(
+        review_requested
+            .as_array()
+            .map(|items| items.iter().map(|item| parse_gitlab_item(item, '!')).collect::<Vec<_>>())
+            .unwrap_or_default(),
+    );
+
+    let authored = fetch_gitlab_json(
+        &client,
+        &token,
+        &format!(
+            "{}/merge_requests?state=opened&scope=created_by_me&per_page={}",
+            api_url, FORGE_PAGE_SIZE
+        ),
+    )
+    .await?;
+    // The list endpoint omits pipelines, so look up the latest one per merge request
+    for item in authored.as_array().cloned().unwrap_or_default() {
+        let mut merge_request = parse_gitlab_item(&item, '!');
+        if let (Some(project_id), Some(iid)) = (item["project_id"].as_u64(), item["iid"].as_u64()) {
+            merge_request.ci_status = fetch_gitlab_json(
+                &client,
+                &token,
+                &format!(
+                    "{}/projects/{}/merge_requests/{}/pipelines?per_page=1",
+                    api_url, project_id, iid
+                ),
+            )
+            .await
+            .ok()
+            .and_then(|pipelines| latest_gitlab_pipeline_status(&pipelines));
+        }
+        overview.authored.push(merge_request);
+    }
+
+    let issues = fetch_gitlab_json(
+        &client,
+        &token,
+        &format!(
+            "{}/issues?state=opened&scope=assigned_to_me&per_page={}",
+            api_url, FORGE_PAGE_SIZE
+        ),
+    )
+    .await?;
+    overview.assigned_issues.extend(
+        issues
+            .as_array()
+            .map(|items| items.iter().map(|item| parse_gitlab_item(item, '#')).collect::<Vec<_>>())
+            .unwrap_or_default(),
+    );
+
+    Ok(())
+}
+
+async fn collect_forge_overview() -> Result<ForgeOverview, String> {
+    if env::var("GITHUB_TOKEN").is_err() && env::var("GITLAB_TOKEN").is_err() {
+        return Err("Neither GITHUB_TOKEN nor GITLAB_TOKEN environment variable set".to_string());
+    }
+
+    let mut overview = ForgeOverview::default();
+
+    if let Err(err) = collect_github_overview(&mut overview).await {
+        overview.errors.push(err);
+    }
+    if let Err(err) = collect_gitlab_overview(&mut overview).await {
+        overview.errors.push(err);
+    }
+
+    // Most recently updated first across both providers
+    for items in [
+        &mut overview.review_requested,
+        &mut overview.authored,
+        &mut overview.assigned_issues,
+    ] {
+        items.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    }
+
+    Ok(overview)
+}
+
+const PIPELINE_RUNS_PER_BRANCH: usize = 5;
+
+fn pipeline_watches() -> Result<Vec<PipelineWatch>, String> {
+    // CI_PIPELINES holds a JSON array of {"provider", "repository", "branch", "notify"} objects
+    let raw = env::var("CI_PIPELINES")
+        .map_err(|_| "CI_PIPELINES environment variable not set".to_string())?;
+    let watches: Vec<PipelineWatch> =
+        serde_json::from_str(&raw).map_err(|e| format!("Failed to parse CI_PIPELINES: {}", e))?;
+    if watches.is_empty() {
+        return Err("CI_PIPELINES does not contain any pipelines".to_string());
+    }
+    Ok(watches)
+}
+
+// Seconds between two RFC 3339 timestamps, or until now when `end` is missing
+fn duration_between(start: Option<&str>, end: Option<&str>) -> Option<i64> {
+    let start = DateTime::parse_from_rfc3339(start?).ok()?.with_timezone(&Utc);
+    let end = match end {
+        Some(end) => DateTime::parse_from_rfc3339(end).ok()?.with_timezone(&Utc),
+        None => Utc::now(),
+    };
+    Some((end - start).num_seconds().max(0))
+}
+
+async fn fetch_github_json(
+    client: &reqwest::Client,
+    token: &str,
+    url: &str,
+) -> Result<serde_json::Value, String> {
+    let response = client
+        .get(url)
+        .bearer_auth(token)
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "dashboard")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch GitHub data: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("GitHub API error ({}): {}", status, body));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse GitHub response: {}", e))
+}
+
+// When the last job completed and which jobs failed
+fn summarize_github_jobs(jobs: &serde_json::Value) -> (Option<String>, Vec<String>) {
+    let jobs = jobs["jobs"].as_array().map(Vec::as_slice).unwrap_or_default();
+    let completed_at = jobs
+        .iter()
+        .filter_map(|job| job["completed_at"].as_str())
+        .filter_map(|completed_at| DateTime::parse_from_rfc3339(completed_at).ok())
+        .max()
+        .map(|completed_at| completed_at.to_rfc3339());
+    let failed_jobs = jobs
+        .iter()
+        .filter(|job| {
+            job["conclusion"].as_str().and_then(normalize_ci_status).as_deref() == Some("failure")
+        })
+        .filter_map(|job| job["name"].as_str().map(str::to_string))
+        .collect();
+    (completed_at, failed_jobs)
+}
+
+async fn collect_github_pipeline_runs(
+    client: &reqwest::Client,
+    watch: &PipelineWatch,
+) -> Result<Vec<PipelineRun>, String> {
+    let token = env::var("GITHUB_TOKEN")
+        .map_err(|_| "GITHUB_TOKEN environment variable not set".to_string())?;
+    let repo_url = format!("{}/repos/{}", github_api_url(), watch.repository);
+
+    let json = fetch_github_json(
+        client,
+        &token,
+        &format!(
+            "{}/actions/runs?branch={}&per_page={}",
+            repo_url,
+            urlencoding::encode(&watch.branch),
+            PIPELINE_RUNS_PER_BRANCH
+        ),
+    )
+    .await?;
+
+    let mut runs: Vec<PipelineRun> = json["workflow_runs"]
+        .as_array()
+        .ok_or("Invalid GitHub response format: missing 'workflow_runs'")?
+        .iter()
+        .map(|run| {
+            let completed = run["status"].as_str() == Some("completed");
+            let status = if completed {
+                run["conclusion"]
+                    .as_str()
+                    .and_then(normalize_ci_status)
+                    .unwrap_or_else(|| "neutral".to_string())
+            } else {
+                "pending".to_string()
+            };
+            let started_at = run["run_started_at"].as_str();
+
+            PipelineRun {
+                id: run["id"].as_u64().unwrap_or(0),
+                name: run["name"].as_str().unwrap_or("Workflow").to_string(),
+                status,
+                url: run["html_url"].as_str().unwrap_or("").to_string(),
+                commit_sha: run["head_sha"].as_str().unwrap_or("").to_string(),
+                commit_message: run["head_commit"]["message"]
+                    .as_str()
+                    .map(|message| message.lines().next().unwrap_or("").to_string()),
+                triggered_by: run["triggering_actor"]["login"]
+                    .as_str()
+                    .or_else(|| run["actor"]["login"].as_str())
+                    .map(str::to_string),
+                started_at: started_at.map(str::to_string),
+                // Finished runs are timed from their jobs below
+                duration_secs: if completed { None } else { duration_between(started_at, None) },
+                failed_jobs: Vec::new(),
+                jobs_error: None,
+            }
+        })
+        .collect();
+
+    // `updated_at` moves on re-runs and later metadata edits, so a finished run
+    // lasts from its start until the last job of the latest attempt completed
+    for (index, run) in runs.iter_mut().enumerate() {
+        if run.status == "pending" {
+            continue;
+        }
+        // Missing jobs must not hide the run itself
+        match fetch_github_json(
+            client,
+            &token,
+            &format!("{}/actions/runs/{}/jobs?filter=latest", repo_url, run.id),
+        )
+        .await
+        {
+            Ok(jobs) => {
+                let (completed_at, failed_jobs) = summarize_github_jobs(&jobs);
+                run.duration_secs =
+                    duration_between(run.started_at.as_deref(), completed_at.as_deref());
+                if index == 0 && run.status == "failure" {
+                    run.failed_jobs = failed_jobs;
+                }
+            }
+            Err(err) => run.jobs_error = Some(format!("Failed to load jobs: {}", err)),
+        }
+    }
+
+    Ok(runs)
+}
+
+async fn collect_gitlab_pipeline_runs(
+    client: &reqwest::Client,
+    watch: &PipelineWatch,
+) -> Result<Vec<PipelineRun>, String> {
+    let token = env::var("GITLAB_TOKEN")
+        .map_err(|_| "GITLAB_TOKEN environment variable not set".to_string())?;
+    let project_url = format!(
+        "{}/projects/{}",
+        gitlab_api_url(),
+        urlencoding::encode(&watch.repository)
+    );
+
+    let json = fetch_gitlab_json(
+        client,
+        &token,
+        &format!(
+            "{}/pipelines?ref={}&per_page={}",
+            project_url,
+            urlencoding::encode(&watch.branch),
+            PIPELINE_RUNS_PER_BRANCH
+        ),
+    )
+    .await?;
+
+    let mut runs = Vec::new();
+    // The list endpoint omits duration and user, so load each pipeline's details
+    for summary in json
+        .as_array()
+        .ok_or("Invalid GitLab response format: expected a list of pipelines")?
+    {
+        let id = summary["id"].as_u64().unwrap_or(0);
+        let pipeline = fetch_gitlab_json(client, &token, &format!("{}/pipelines/{}", project_url, id))
+            .await
+            .unwrap_or_else(|_| summary.clone());
+        let raw_status = pipeline["status"].as_str().unwrap_or("");
+        let finished_at = pipeline["finished_at"].as_str();
+
+        runs.push(PipelineRun {
+            id,
+            name: pipeline["name"]
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("Pipeline #{}", id)),
+            status: normalize_ci_status(raw_status).unwrap_or_else(|| "neutral".to_string()),
+            url: pipeline["web_url"].as_str().unwrap_or("").to_string(),
+            commit_sha: pipeline["sha"].as_str().unwrap_or("").to_string(),
+            commit_message: None,
+            triggered_by: pipeline["user"]["username"].as_str().map(str::to_string),
+            started_at: pipeline["started_at"].as_str().map(str::to_string),
+            duration_secs: pipeline["duration"]
+                .as_i64()
+                .or_else(|| duration_between(pipeline["started_at"].as_str(), finished_at)),
+            failed_jobs: Vec::new(),
+            jobs_error: None,
+        });
+    }
+
+    if let Some(latest) = runs.first_mut() {
+        // Pipelines do not carry the commit title, fetch it for the latest run only
+        if !latest.commit_sha.is_empty() {
+            latest.commit_message = fetch_gitlab_json(
+                client,
+                &token,
+                &format!("{}/repository/commits/{}", project_url, latest.commit_sha),
+            )
+            .await
+            .ok()
+            .and_then(|commit| commit["title"].as_str().map(str::to_string));
+        }
+
+        if latest.status == "failure" {
+            // Missing job names must not hide the failed pipeline itself
+            match fetch_gitlab_json(
+                client,
+                &token,
+                &format!("{}/pipelines/{}/jobs?scope[]=failed", project_url, latest.id),
+            )
+            .await
+            {
+                Ok(jobs) => {
+                    latest.failed_jobs = jobs
+                        .as_array()
+                        .map(|jobs| {
+                            jobs.iter()
+                                .filter_map(|job| job["name"].as_str().map(str::to_string))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                }
+                Err(err) => latest.jobs_error = Some(format!("Failed to load jobs: {}", err)),
+            }
+        }
+    }
+
+    Ok(runs)
+}
+
+async fn collect_pipeline_statuses() -> Result<Vec<PipelineStatus>, String> {
+    let watches = pipeline_watches()?;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+
+    let mut statuses = Vec::new();
+    for watch in watches {
+        // A broken repository is reported on its own entry instead of failing the whole list
+        let runs = match watch.provider.to_lowercase().as_str() {
+            "github" => collect_github_pipeline_runs(&client, &watch).await,
+            "gitlab" => collect_gitlab_pipeline_runs(&client, &watch).await,
+            other => Err(format!("Unknown CI provider '{}'", other)),
+        };
+
+        let (runs, error) = match runs {
+            Ok(runs) => (runs, None),
+            Err(err) => (Vec::new(), Some(err)),
+        };
+        statuses.push(PipelineStatus {
+            provider: watch.provider,
+            repository: watch.repository,
+            branch: watch.branch,
+            runs,
+            error,
+        });
+    }
+
+    Ok(statuses)
+}
+
+// The latest run that finished, ignoring pending ones so a running pipeline
+// does not reset the branch state
+fn latest_finished_run(status: &PipelineStatus) -> Option<&PipelineRun> {
+    status.runs.iter().find(|run| run.status != "pending")
+}
+
+fn diff_pipeline_statuses(
+    previous: &[PipelineStatus],
+    current: &[PipelineStatus],
+) -> Vec<PipelineFailure> {
+    let Ok(watches) = pipeline_watches() else {
+        return Vec::new();
+    };
+
+    current
+        .iter()
+        .filter(|status| {
+            watches.iter().any(|watch| {
+                watch.provider == status.provider
+                    && watch.repository == status.repository
+                    && watch.branch == status.branch
+                    && watch.notifies()
+            })
+        })
+        .filter_map(|status| {
+            let latest = latest_finished_run(status).filter(|run| run.status == "failure")?;
+            let was_failing = previous
+                .iter()
+                .find(|p| {
+                    p.provider == status.provider
+                        && p.repository == status.repository
+                        && p.branch == status.branch
+                })
+                .and_then(latest_finished_run)
+                // Unknown previous state counts as failing to avoid notifying on startup
+                .is_none_or(|run| run.status == "failure");
+
+            (!was_failing).then(|| PipelineFailure {
+                provider: status.provider.clone(),
+                repository: status.repository.clone(),
+                branch: status.branch.clone(),
+                url: latest.url.clone(),
+                commit_message: latest.commit_message.clone(),
+                failed_jobs: latest.failed_jobs.clone(),
+            })
+        })
+        .collect()
+}
+
+// Returns delivery failures per failing branch so they show up on its status
+fn notify_pipeline_failures(
+    app: &AppHandle,
+    failures: &[PipelineFailure],
+) -> Vec<(PipelineFailure, String)> {
+    let mut errors = Vec::new();
+    for failure in failures {
+        let body = if failure.failed_jobs.is_empty() {
+            failure.commit_message.clone().unwrap_or_default()
+        } else {
+            format!("Failed jobs: {}", failure.failed_jobs.join(", "))
+        };
+
+        if let Err(err) = app
+            .notification()
+            .builder()
+            .title(format!("{} {} is failing", failure.repository, failure.branch))
+            .body(body)
+            .show()
+        {
+            errors.push((
+                failure.clone(),
+                format!("Failed to show pipeline notification: {}", err),
+            ));
+        }
+    }
+
+    if let Err(err) = app.emit("pipeline-failures", failures.to_vec()) {
+        errors.extend(
+            failures
+                .iter()
+                .map(|failure| (failure.clone(), format!("Failed to emit pipeline failures: {}", err))),
+        );
+    }
+    errors
+}
+
+fn find_git_repositories(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) {
+    if dir.join(".git").exists() {
+        found.push(dir.to_path_buf());
+        return;
+    }
+    if depth == 0 {
+        return;
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        if file_name.starts_with('.') || file_name == "node_modules" || file_name == "target" {
+            continue;
+        }
+        if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            find_git_repositories(&entry.path(), depth - 1, found);
+        }
+    }
+}
+
+fn read_git_repository(path: &Path) -> Result<GitRepoStatus, git2::Error> {
+    let mut repo = git2::Repository::open(path)?;
+    let mut status = GitRepoStatus {
+        name: path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string()),
+        path: path.display().to_string(),
+        ..Default::default()
+    };
+
+    // A fresh repository has no HEAD commit yet, which is not an error here
+    if let Ok(head) = repo.head() {
+        if head.is_branch() {
+            status.branch = head.shorthand().map(str::to_string);
+        }
+
+        if let Ok(commit) = head.peel_to_commit() {
+            status.last_commit_time_ms = Some(commit.time().seconds() * 1000);
+            status.last_commit_summary = commit.summary().map(str::to_string);
+        }
+
+        if let Some(branch_name) = &status.branch {
+            let local = repo.find_branch(branch_name, git2::BranchType::Local)?;
+            if let Ok(upstream) = local.upstream() {
+                status.upstream = upstream.name()?.map(str::to_string);
+                if let (Some(local_oid), Some(upstream_oid)) =
+                    (local.get().target(), upstream.get().target())
+                {
+                    let (ahead, behind) = repo.graph_ahead_behind(local_oid, upstream_oid)?;
+                    status.ahead = ahead;
+                    status.behind = behind;
+                }
+            }
+        }
+    }
+
+    let mut options = git2::StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .exclude_submodules(true);
+    for entry in repo.statuses(Some(&mut options))?.iter() {
+        let flags = entry.status();
+        if flags.is_conflicted() {
+            status.conflicted_files += 1;
+            continue;
+        }
+        if flags.intersects(
+            git2::Status::INDEX_NEW
+                | git2::Status::INDEX_MODIFIED
+                | git2::Status::INDEX_DELETED
+                | git2::Status::INDEX_RENAMED
+                | git2::Status::INDEX_TYPECHANGE,
+        ) {
+            status.staged_files += 1;
+        }
+        if flags.is_wt_new() {
+            status.untracked_files += 1;
+        } else if flags.intersects(
+            git2::Status::WT_MODIFIED
+                | git2::Status::WT_DELETED
+                | git2::Status::WT_RENAMED
+                | git2::Status::WT_TYPECHANGE,
+        ) {
+            status.modified_files += 1;
+        }
+    }
+
+    repo.stash_foreach(|_, _, _| {
+        status.stash_count += 1;
+        true
+    })?;
+
+    Ok(status)
+}
+
+fn collect_git_repositories() -> Result<Vec<GitRepoStatus>, String> {
+    // GIT_REPOS_DIRS uses the platform path separator (":" or ";"), like PATH
+    let raw = env::var_os("GIT_REPOS_DIRS")
+        .ok_or("GIT_REPOS_DIRS environment variable not set")?;
+    let depth = env::var("GIT_SCAN_DEPTH")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(2);
+
+    let mut paths = Vec::new();
+    for dir in env::split_paths(&raw) {
+        find_git_repositories(&dir, depth, &mut paths);
+    }
+    paths.sort();
+    paths.dedup();
+
+    Ok(paths
+        .iter()
+        .map(|path| {
+            read_git_repository(path).unwrap_or_else(|err| GitRepoStatus {
+                name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                path: path.display().to_string(),
+                error: Some(err.message().to_string()),
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+const DEFAULT_HEALTH_CHECK_TIMEOUT_MS: u64 = 8000;
+
+const HEALTH_HISTORY_RETENTION_MS: u128 = 30 * 24 * 60 * 60 * 1000;
+const HEALTH_HISTORY_COMPACT_AFTER: usize = 1000;
+const HOUR_MS: u128 = 60 * 60 * 1000;
+
+// Every check result, appended to a JSON lines file so a month of 20 second
+// checks does not rewrite the whole history on each round
+#[derive(Default)]
+struct HealthHistory {
+    samples: HashMap<String, VecDeque<HealthSample>>,
+    path: Option<PathBuf>,
+    // Samples pruned from memory but still present in the file
+    pruned_since_compaction: usize,
+    // Lines recorded since the last write, flushed by the caller outside the lock
+    unsaved: String,
+    // Failure of the last file write, shown until a write succeeds again
+    write_error: Option<String>,
+}
+
+// File update taken out of the history so it can run on a blocking thread
+enum HealthHistoryWrite {
+    Append(PathBuf, String),
+    Replace(PathBuf, String),
+}
+
+impl HealthHistoryWrite {
+    fn apply(self) -> Result<(), String> {
+        use std::io::Write;
+
+        match self {
+            HealthHistoryWrite::Append(path, lines) => {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .and_then(|mut file| file.write_all(lines.as_bytes()))
+                    .map_err(|e| format!("Failed to save health history: {}", e))
+            }
+            HealthHistoryWrite::Replace(path, lines) => write_file_atomically(&path, lines.as_bytes())
+                .map_err(|e| format!("Failed to compact health history: {}", e)),
+        }
+    }
+}
+
+impl HealthHistory {
+    fn load(path: PathBuf) -> Self {
+        let cutoff = unix_time_ms().saturating_sub(HEALTH_HISTORY_RETENTION_MS);
+        let mut samples: HashMap<String, VecDeque<HealthSample>> = HashMap::new();
+
+        if let Ok(contents) = std::fs::read_to_string(&path) {
+            for sample in contents
+                .lines()
+                .filter_map(|line| serde_json::from_str::<HealthSample>(line).ok())
+                .filter(|sample| sample.checked_at_ms >= cutoff)
+            {
+                samples.entry(sample.name.clone()).or_default().push_back(sample);
+            }
+        }
+
+        let mut history = Self {
+            samples,
+            path: Some(path),
+            ..Default::default()
+        };
+        // Runs once during setup, before any poller touches the file
+        if let Some(write) = history.compaction() {
+            history.write_error = write.apply().err();
+        }
+        history
+    }
+
+    fn record(&mut self, health: &ServiceHealth, state: &str) {
+        let sample = HealthSample {
+            name: health.name.clone(),
+            checked_at_ms: health.checked_at_ms,
+            state: state.to_string(),
+            latency_ms: health.latency_ms,
+            error: health.error.clone(),
+        };
+        if let Ok(line) = serde_json::to_string(&sample) {
+            self.unsaved.push_str(&line);
+            self.unsaved.push('\n');
+        }
+
+        let cutoff = sample.checked_at_ms.saturating_sub(HEALTH_HISTORY_RETENTION_MS);
+        let entries = self.samples.entry(sample.name.clone()).or_default();
+        entries.push_back(sample);
+        while entries.front().is_some_and(|s| s.checked_at_ms < cutoff) {
+            entries.pop_front();
+            self.pruned_since_compaction += 1;
+        }
+    }
+
+    // The lines recorded since the last call, or a full rewrite once enough
+    // samples were pruned
+    fn pending_write(&mut self) -> Option<HealthHistoryWrite> {
+        if self.pruned_since_compaction >= HEALTH_HISTORY_COMPACT_AFTER {
+            return self.compaction();
+        }
+        if self.unsaved.is_empty() {
+            return None;
+        }
+        let lines = std::mem::take(&mut self.unsaved);
+        Some(HealthHistoryWrite::Append(self.path.clone()?, lines))
+    }
+
+    // Rewrites the file with only the samples still kept in memory
+    fn compaction(&mut self) -> Option<HealthHistoryWrite> {
+        let path = self.path.clone()?;
+
+        let mut lines = String::new();
+        for sample in self.samples.values().flatten() {
+            if let Ok(line) = serde_json::to_string(sample) {
+                lines.push_str(&line);
+                lines.push('\n');
+            }
+        }
+
+        // The rewrite already contains everything recorded so far
+        self.unsaved.clear();
+        self.pruned_since_compaction = 0;
+        Some(HealthHistoryWrite::Replace(path, lines))
+    }
+
+    fn uptime(&self, name: &str, window_hours: u128, bucket_count: usize) -> ServiceUptime {
+        let now = unix_time_ms();
+        let empty = VecDeque::new();
+        let samples = self.samples.get(name).unwrap_or(&empty);
+
+        let since = |window_ms: u128| {
+            let cutoff = now.saturating_sub(window_ms);
+            samples.iter().filter(move |s| s.checked_at_ms >= cutoff)
+        };
+
+        let mut latencies: Vec<u128> = since(window_hours * HOUR_MS)
+            .filter(|s| s.state != "down")
+            .filter_map(|s| s.latency_ms)
+            .collect();
+        latencies.sort_unstable();
+
+        let window_ms = window_hours * HOUR_MS;
+        let bucket_count = bucket_count.max(1);
+        let bucket_ms = (window_ms / bucket_count as u128).max(1);
+        let window_start = now.saturating_sub(window_ms);
+
+        // One pass over the samples, each lands in the bucket its time falls into
+        let mut in_buckets: Vec<Vec<&HealthSample>> = vec![Vec::new(); bucket_count];
+        for sample in since(window_ms) {
+            let index = ((sample.checked_at_ms - window_start) / bucket_ms) as usize;
+            if let Some(bucket) = in_buckets.get_mut(index) {
+                bucket.push(sample);
+            }
+        }
+
+        let buckets = in_buckets
+            .into_iter()
+            .enumerate()
+            .map(|(index, in_bucket)| {
+                let start_ms = window_start + bucket_ms * index as u128;
+                let state = ["down", "degraded", "up"]
+                    .into_iter()
+                    .find(|state| in_bucket.iter().any(|s| s.state == *state))
+                    .map(str::to_string);
+
+                UptimeBucket {
+                    start_ms,
+                    end_ms: start_ms + bucket_ms,
+                    uptime_percent: uptime_percent(in_bucket.into_iter()),
+                    state,
+                }
+            })
+            .collect();
+
+        ServiceUptime {
+            name: name.to_string(),
+            uptime_24h: uptime_percent(since(24 * HOUR_MS)),
+            uptime_7d: uptime_percent(since(7 * 24 * HOUR_MS)),
+            uptime_30d: uptime_percent(since(30 * 24 * HOUR_MS)),
+            latency_p50_ms: percentile(&latencies, 50.0),
+            latency_p95_ms: percentile(&latencies, 95.0),
+            latency_p99_ms: percentile(&latencies, 99.0),
+            buckets,
+            incidents: outage_incidents(samples, now),
+        }
+    }
+}
+
+// Degraded checks count as up, only "down" reduces uptime
+fn uptime_percent<'a>(samples: impl Iterator<Item = &'a HealthSample>) -> Option<f64> {
+    let (total, up) = samples.fold((0usize, 0usize), |(total, up), sample| {
+        (total + 1, up + usize::from(sample.state != "down"))
+    });
+    (total > 0).then(|| up as f64 * 100.0 / total as f64)
+}
+
+// Nearest-rank percentile over sorted values
+fn percentile(sorted: &[u128], percentile: f64) -> Option<u128> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.clamp(1, sorted.len()) - 1).copied()
+}
+
+fn outage_incidents(samples: &VecDeque<HealthSample>, now: u128) -> Vec<OutageIncident> {
+    let mut incidents = Vec::new();
+    let mut current: Option<OutageIncident> = None;
+
+    for sample in samples {
+        match (&mut current, sample.state == "down") {
+            (None, true) => {
+                current = Some(OutageIncident {
+                    started_at_ms: sample.checked_at_ms,
+                    ended_at_ms: None,
+                    duration_ms: 0,
+                    error: sample.error.clone(),
+                });
+            }
+            (Some(incident), false) => {
+                incident.ended_at_ms = Some(sample.checked_at_ms);
+                incident.duration_ms = sample.checked_at_ms.saturating_sub(incident.started_at_ms);
+                incidents.extend(current.take());
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(mut incident) = current {
+        incident.duration_ms = now.saturating_sub(incident.started_at_ms);
+        incidents.push(incident);
+    }
+
+    // Most recent first
+    incidents.reverse();
+    incidents
+}
+
+fn health_check_configs() -> Result<Vec<HealthCheckConfig>, String> {
+    // HEALTH_CHECKS holds a JSON array of {"name", "url", "kind", "timeout_ms"} objects
+    // plus the optional request, assertion and policy fields
+    if let Ok(raw) = env::var("HEALTH_CHECKS") {
+        if !raw.trim().is_empty() {
+            return parse_health_check_configs(&raw);
+        }
+    }
+
+    Ok([
+        ("Trisolaris", "https://app.florianraith.com/up"),
+        ("Spliit", "https://spliit.florianraith.com/api/health"),
+        ("Partnerportal (Dev)", "https://dev-portal.zewotherm.com/up"),
+        ("Partnerportal (Prod)", "https://portal.zewotherm.com/up"),
+    ]
+    .into_iter()
+    .map(|(name, url)| HealthCheckConfig {
+        name: name.to_string(),
+        url: url.to_string(),
+        kind: default_health_check_kind(),
+        timeout_ms: None,
+        tls_warn_days: None,
+        assertions: HealthAssertions::default(),
+        policy: HealthPolicy::default(),
+        method: None,
+        headers: HashMap::new(),
+        body: None,
+        auth: None,
+        auth_secret: None,
+    })
+    .collect())
+}
+
+fn parse_health_check_configs(raw: &str) -> Result<Vec<HealthCheckConfig>, String> {
+    serde_json::from_str(raw).map_err(|e| format!("Failed to parse HEALTH_CHECKS: {}", e))
+}
+
+fn json_value_at<'a>(json: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    if path.starts_with('/') {
+        return json.pointer(path);
+    }
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(json, |value, segment| match segment.parse::<usize>() {
+            Ok(index) if value.is_array() => value.get(index),
+            _ => value.get(segment),
+        })
+}
+
+// Splits failed assertions into those that take the service down and those
+// that only degrade it
+fn evaluate_health_assertions(
+    assertions: &HealthAssertions,
+    status: u16,
+    headers: &reqwest::header::HeaderMap,
+    body: Option<&str>,
+    latency_ms: u128,
+) -> (Vec<String>, Vec<String>) {
+    let mut down = Vec::new();
+    let mut content = Vec::new();
+    let mut degraded = Vec::new();
+
+    let status_ok = if assertions.expected_status.is_empty() {
+        (200..300).contains(&status)
+    } else {
+        assertions.expected_status.contains(&status)
+    };
+    if !status_ok {
+        down.push(format!("Unexpected status code {}", status));
+    }
+
+    for (name, expected) in &assertions.required_headers {
+        match (headers.get(name).and_then(|v| v.to_str().ok()), expected) {
+            (None, _) => content.push(format!("Missing header '{}'", name)),
+            (Some(actual), Some(expected)) if actual != expected => content.push(format!(
+                "Header '{}' is '{}', expected '{}'",
+                name, actual, expected
+            )),
+            _ => {}
+        }
+    }
+
+    if let Some(body) = body {
+        if let Some(needle) = &assertions.body_contains {
+            if !body.contains(needle.as_str()) {
+                content.push(format!("Body does not contain '{}'", needle));
+            }
+        }
+
+        if let Some(pattern) = &assertions.body_regex {
+            match regex::Regex::new(pattern) {
+                Ok(re) if !re.is_match(body) => {
+                    content.push(format!("Body does not match /{}/", pattern))
+                }
+                Ok(_) => {}
+                Err(err) => down.push(format!("Invalid body_regex: {}", err)),
+            }
+        }
+
+        if let Some(path) = &assertions.json_path {
+            match serde_json::from_str::<serde_json::Value>(body) {
+                Ok(json) => match (json_value_at(&json, path), &assertions.json_equals) {
+                    (None, _) => content.push(format!("JSON path '{}' not found", path)),
+                    (Some(actual), Some(expected)) if actual != expected => content.push(
+                        format!("JSON path '{}' is {}, expected {}", path, actual, expected),
+                    ),
+                    _ => {}
+                },
+                Err(err) => content.push(format!("Body is not valid JSON: {}", err)),
+            }
+        }
+    }
+
+    if let Some(max_latency_ms) = assertions.max_latency_ms {
+        if latency_ms as u64 > max_latency_ms {
+            degraded.push(format!("Latency {}ms exceeds {}ms", latency_ms, max_latency_ms));
+        }
+    }
+
+    if assertions.degraded_on_content_mismatch {
+        degraded.extend(content);
+    } else {
+        down.extend(content);
+    }
+
+    (down, degraded)
+}
+
+fn build_health_request(
+    client: &reqwest::Client,
+    service: &HealthCheckConfig,
+) -> Result<reqwest::RequestBuilder, String> {
+    let method = match &service.method {
+        Some(method) => reqwest::Method::from_bytes(method.trim().to_uppercase().as_bytes())
+            .map_err(|_| format!("Invalid HTTP method '{}'", method))?,
+        None => reqwest::Method::GET,
+    };
+
+    let mut request = client.request(method, &service.url);
+    for (name, value) in &service.headers {
+        request = request.header(name, value);
+    }
+    if let Some(body) = &service.body {
+        request = request.body(body.clone());
+    }
+
+    if let Some(auth) = &service.auth {
+        let secret = service
+            .auth_secret
+            .clone()
+            .unwrap_or_else(|| Err("Credentials were not loaded".to_string()))?;
+        request = match auth {
+            HealthCheckAuth::Bearer { .. } => request.bearer_auth(secret),
+            HealthCheckAuth::Basic { username, .. } => request.basic_auth(username, Some(secret)),
+            HealthCheckAuth::Header { name, .. } => request.header(name, secret),
+        };
+    }
+
+    Ok(request)
+}
+
+async fn check_http_service(
+    client: &reqwest::Client,
+    service: &HealthCheckConfig,
+    timeout: Duration,
+    checked_at_ms: u128,
+) -> ServiceHealth {
+    let mut health = ServiceHealth::new(service, checked_at_ms);
+    let request = match build_health_request(client, service) {
+        Ok(request) => request,
+        Err(err) => {
+            health.apply_failures(vec![err], Vec::new());
+            return health;
+        }
+    };
+
+    let started = std::time::Instant::now();
+    let response = request.timeout(timeout).send().await;
+
+    match response {
+        Ok(resp) => {
+            // Latency covers the time until the headers arrived, not the body download
+            let latency_ms = started.elapsed().as_millis();
+            let status = resp.status();
+            let headers = resp.headers().clone();
+            let body = if service.assertions.needs_body() {
+                Some(resp.text().await.unwrap_or_default())
+            } else {
+                None
+            };
+
+            let (down, degraded) = evaluate_health_assertions(
+                &service.assertions,
+                status.as_u16(),
+                &headers,
+                body.as_deref(),
+                latency_ms,
+            );
+            health.status_code = Some(status.as_u16());
+            health.latency_ms = Some(latency_ms);
+            health.apply_failures(down, degraded);
+        }
+        Err(err) => {
+            health.latency_ms = Some(started.elapsed().as_millis());
+            health.apply_failures(vec![err.to_string()], Vec::new());
+        }
+    }
+
+    health
+}
+
+// Accepts "host", "host:port" or a URL such as "https://host:8443/path"
+fn probe_target(target: &str, default_port: u16) -> Result<(String, u16), String> {
+    if target.contains("://") {
+        let url = reqwest::Url::parse(target)
+            .map_err(|e| format!("Invalid target '{}': {}", target, e))?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| format!("Target '{}' has no host", target))?;
+        return Ok((
+            host.trim_start_matches('[').trim_end_matches(']').to_string(),
+            url.port_or_known_default().unwrap_or(default_port),
+        ));
+    }
+
+    // "[::1]:6379" or "[::1]"
+    if let Some(rest) = target.strip_prefix('[') {
+        let (host, port) = rest
+            .split_once(']')
+            .ok_or_else(|| format!("Missing ']' in target '{}'", target))?;
+        let port = match port {
+            "" => default_port,
+            port => port
+                .strip_prefix(':')
+                .and_then(|port| port.parse().ok())
+                .ok_or_else(|| format!("Invalid port in target '{}'", target))?,
+        };
+        return Ok((host.to_string(), port));
+    }
+
+    // A bare IPv6 address cannot carry a port, "::1:6379" is itself an address
+    if target.matches(':').count() > 1 {
+        return match target.parse::<std::net::Ipv6Addr>() {
+            Ok(_) => Ok((target.to_string(), default_port)),
+            Err(_) => Err(format!(
+                "Invalid target '{}', IPv6 addresses with a port need brackets like [::1]:6379",
+                target
+            )),
+        };
+    }
+
+    match target.rsplit_once(':') {
+        Some((host, port)) => port
+            .parse()
+            .map(|port| (host.to_string(), port))
+            .map_err(|_| format!("Invalid port in target '{}'", target)),
+        None => Ok((target.to_string(), default_port)),
+    }
+}
+
+async fn probe_tcp(target: &str) -> Result<ProbeOutcome, String> {
+    let (host, port) = probe_target(target, 0)?;
+    if port == 0 {
+        return Err(format!(
+            "TCP target '{}' needs a port, write IPv6 addresses as [::1]:6379",
+            target
+        ));
+    }
+
+    let stream = tokio::net::TcpStream::connect((host.as_str(), port))
+        .await
+        .map_err(|e| format!("Failed to connect to {}:{}: {}", host, port, e))?;
+
+    Ok(ProbeOutcome {
+        details: stream
+            .peer_addr()
+            .ok()
+            .map(|addr| format!("Connected to {}", addr)),
+        ..Default::default()
+    })
+}
+
+async fn probe_dns(target: &str) -> Result<ProbeOutcome, String> {
+    let (host, _) = probe_target(target, 0)?;
+
+    let mut addresses: Vec<String> = tokio::net::lookup_host((host.as_str(), 0))
+        .await
+        .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        .map(|addr| addr.ip().to_string())
+        .collect();
+    addresses.sort();
+    addresses.dedup();
+
+    if addresses.is_empty() {
+        return Err(format!("{} did not resolve to any address", host));
+    }
+
+    Ok(ProbeOutcome {
+        details: Some(addresses.join(", ")),
+        ..Default::default()
+    })
+}
+
+async fn fetch_tls_certificate(host: &str, port: u16, verify: bool) -> Result<Vec<u8>, String> {
+    let connector = tokio_native_tls::native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(!verify)
+        .danger_accept_invalid_hostnames(!verify)
+        .build()
+        .map_err(|e| format!("Failed to create TLS connector: {}", e))?;
+    let connector = tokio_native_tls::TlsConnector::from(connector);
+
+    let stream = tokio::net::TcpStream::connect((host, port))
+        .await
+        .map_err(|e| format!("Failed to connect to {}:{}: {}", host, port, e))?;
+    let tls = connector
+        .connect(host, stream)
+        .await
+        .map_err(|e| format!("TLS handshake failed: {}", e))?;
+
+    tls.get_ref()
+        .peer_certificate()
+        .map_err(|e| format!("Failed to read certificate: {}", e))?
+        .ok_or("Server did not present a certificate")?
+        .to_der()
+        .map_err(|e| format!("Failed to encode certificate: {}", e))
+}
+
+fn x509_name_label(name: &x509_parser::x509::X509Name) -> String {
+    name.iter_common_name()
+        .next()
+        .and_then(|cn| cn.as_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| name.to_string())
+}
+
+async fn probe_tls(target: &str, warn_days: i64) -> Result<ProbeOutcome, String> {
+    let (host, port) = probe_target(target, 443)?;
+    let mut outcome = ProbeOutcome::default();
+
+    // Validate the chain first, but still inspect invalid certificates so an
+    // expired one reports its expiry instead of only a handshake error
+    let der = match fetch_tls_certificate(&host, port, true).await {
+        Ok(der) => der,
+        Err(err) => {
+            outcome.down.push(err);
+            fetch_tls_certificate(&host, port, false).await?
+        }
+    };
+
+    let (_, cert) = x509_parser::parse_x509_certificate(&der)
+        .map_err(|e| format!("Failed to parse certificate: {}", e))?;
+    let expires_at = cert.validity().not_after.timestamp();
+    let days_until_expiry = (expires_at - Utc::now().timestamp()).div_euclid(86400);
+
+    if days_until_expiry < 0 {
+        outcome
+            .down
+            .push(format!("Certificate expired {} days ago", -days_until_expiry));
+    } else if days_until_expiry < warn_days {
+        outcome
+            .degraded
+            .push(format!("Certificate expires in {} days", days_until_expiry));
+    }
+
+    let issuer = x509_name_label(cert.issuer());
+    outcome.details = Some(format!("Expires in {} days, issued by {}", days_until_expiry, issuer));
+    outcome.certificate = Some(TlsCertificateInfo {
+        subject: x509_name_label(cert.subject()),
+        issuer,
+        expires_at_ms: expires_at * 1000,
+        days_until_expiry,
+    });
+
+    Ok(outcome)
+}
+
+async fn check_service(client: &reqwest::Client, service: &HealthCheckConfig) -> ServiceHealth {
+    let timeout = Duration::from_millis(service.timeout_ms.unwrap_or(DEFAULT_HEALTH_CHECK_TIMEOUT_MS));
+    let checked_at_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+
+    let started = std::time::Instant::now();
+    let result = match service.kind.to_lowercase().as_str() {
+        "http" => return check_http_service(client, service, timeout, checked_at_ms).await,
+        "tcp" => tokio::time::timeout(timeout, probe_tcp(&service.url)).await,
+        "dns" => tokio::time::timeout(timeout, probe_dns(&service.url)).await,
+        "tls" => {
+            let warn_days = service.tls_warn_days.unwrap_or(14);
+            tokio::time::timeout(timeout, probe_tls(&service.url, warn_days)).await
+        }
+        other => Ok(Err(format!("Unknown health check kind '{}'", other))),
+    };
+    let latency_ms = started.elapsed().as_millis();
+
+    let mut health = ServiceHealth::new(service, checked_at_ms);
+    health.latency_ms = Some(latency_ms);
+
+    match result {
+        Ok(Ok(mut outcome)) => {
+            if let Some(max_latency_ms) = service.assertions.max_latency_ms {
+                if latency_ms as u64 > max_latency_ms {
+                    outcome.degraded.push(format!(
+                        "Latency {}ms exceeds {}ms",
+                        latency_ms, max_latency_ms
+                    ));
+                }
+            }
+            health.details = outcome.details;
+            health.certificate = outcome.certificate;
+            health.apply_failures(outcome.down, outcome.degraded);
+        }
+        Ok(Err(err)) => health.apply_failures(vec![err], Vec::new()),
+        Err(_) => health.apply_failures(
+            vec![format!("Timed out after {}ms", timeout.as_millis())],
+            Vec::new(),
+        ),
+    }
+
+    health
+}
+
+async fn check_service_with_retries(
+    client: &reqwest::Client,
+    service: &HealthCheckConfig,
+) -> ServiceHealth {
+    let mut health = check_service(client, service).await;
+    let mut attempts = 1;
+
+    while health.state == "down" && attempts <= service.policy.retries() {
+        attempts += 1;
+        health = check_service(client, service).await;
+    }
+
+    health.attempts = attempts;
+    health
+}
+
+// Raw check states per service, used to smooth single failures and detect flapping
+#[derive(Default)]
+struct HealthTracker {
+    recent: HashMap<String, VecDeque<String>>,
+}
+
+impl HealthTracker {
+    // Returns the smoothed state before flapping is applied on top of it
+    fn apply(&mut self, policy: &HealthPolicy, health: &mut ServiceHealth) -> String {
+        let capacity = policy.failure_window().max(policy.flap_window());
+        let recent = self.recent.entry(health.name.clone()).or_default();
+        recent.push_back(health.state.clone());
+        while recent.len() > capacity {
+            recent.pop_front();
+        }
+
+        let failures = recent
+            .iter()
+            .rev()
+            .take(policy.failure_window())
+            .filter(|state| *state == "down")
+            .count();
+        health.recent_failures = failures;
+
+        // Below the threshold a failure keeps the last healthy state, while a
+        // success recovers right away
+        if health.state == "down" && failures < policy.failure_threshold() {
+            health.state = recent
+                .iter()
+                .rev()
+                .skip(1)
+                .find(|state| *state != "down")
+                .cloned()
+                .unwrap_or_else(|| "up".to_string());
+            // The failure is not reported until it crosses the threshold
+            health.error = None;
+            health.failed_assertions.clear();
+        }
+        let smoothed = health.state.clone();
+        health.is_up = smoothed != "down";
+
+        let changes = recent
+            .iter()
+            .rev()
+            .take(policy.flap_window())
+            .map(|state| state == "down")
+            .collect::<Vec<_>>()
+            .windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .count();
+        if changes >= policy.flap_threshold() {
+            health.state = "flapping".to_string();
+        }
+
+        smoothed
+    }
+
+    // Forgets services removed from the config so a reused name starts fresh
+    fn retain(&mut self, names: &[String]) {
+        self.recent.retain(|name, _| names.contains(name));
+    }
+}
+
+fn health_transition_kind(from_state: &str, to_state: &str) -> Option<&'static str> {
+    match (from_state, to_state) {
+        (from, "down") if from != "down" => Some("down"),
+        // Flapping first, going from down to flapping is not a recovery
+        (from, "flapping") if from != "flapping" => Some("flapping"),
+        // A service that settles after flapping recovers too, the last alert said flapping
+        ("down" | "flapping", "up") | ("down" | "flapping", "degraded") => Some("recovered"),
+        _ => None,
+    }
+}
+
+struct AlertConfig {
+    desktop: bool,
+    // Set when HEALTH_ALERT_WEBHOOKS could not be parsed
+    error: Option<String>,
+    webhooks: Vec<AlertWebhook>,
+    cooldown: Duration,
+    // Local (start, end), may wrap around midnight
+    quiet_hours: Option<(chrono::NaiveTime, chrono::NaiveTime)>,
+}
+
+impl AlertConfig {
+    fn from_env() -> Self {
+        let desktop = env::var("HEALTH_ALERT_DESKTOP")
+            .map(|v| !matches!(v.trim().to_lowercase().as_str(), "0" | "false" | "no"))
+            .unwrap_or(true);
+
+        // HEALTH_ALERT_WEBHOOKS holds a JSON array of {"url", "format", "respect_quiet_hours"} objects
+        let mut error = None;
+        let webhooks = env::var("HEALTH_ALERT_WEBHOOKS")
+            .ok()
+            .filter(|raw| !raw.trim().is_empty())
+            .and_then(|raw| match serde_json::from_str(&raw) {
+                Ok(webhooks) => Some(webhooks),
+                Err(err) => {
+                    error = Some(format!("Failed to parse HEALTH_ALERT_WEBHOOKS: {}", err));
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        let cooldown = Duration::from_secs(
+            env::var("HEALTH_ALERT_COOLDOWN_SECS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(300),
+        );
+
+        // HEALTH_ALERT_QUIET_HOURS looks like "22:00-07:00"
+        let quiet_hours = env::var("HEALTH_ALERT_QUIET_HOURS").ok().and_then(|raw| {
+            let (start, end) = raw.trim().split_once('-')?;
+            let parse = |time: &str| chrono::NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+            Some((parse(start)?, parse(end)?))
+        });
+
+        Self {
+            desktop,
+            error,
+            webhooks,
+            cooldown,
+            quiet_hours,
+        }
+    }
+
+    fn in_quiet_hours(&self) -> bool {
+        let Some((start, end)) = self.quiet_hours else {
+            return false;
+        };
+        let now = chrono::Local::now().time();
+        if start <= end {
+            now >= start && now < end
+        } else {
+            now >= start || now < end
+        }
+    }
+}
+
+// Turns health transitions into desktop notifications and webhook calls
+struct HealthAlerter {
+    client: reqwest::Client,
+    // Time and kind of the last alert that went out per service, for cooldowns
+    last_sent: HashMap<String, (std::time::Instant, String)>,
+    // Delivery failures of the latest dispatch, webhooks add theirs when they finish
+    errors: Arc<Mutex<Vec<String>>>,
+}
+
+impl HealthAlerter {
+    fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_else(|_| reqwest::Client::new()),
+            last_sent: HashMap::new(),
+            errors: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    fn errors(&self) -> Vec<String> {
+        self.errors.lock().map(|errors| errors.clone()).unwrap_or_default()
+    }
+
+    fn report(&self, error: String) {
+        if let Ok(mut errors) = self.errors.lock() {
+            errors.push(error);
+        }
+    }
+
+    // One alert per service and cooldown, except that a recovery always closes
+    // an alert that went out. A recovery for a suppressed outage stays quiet
+    fn retain(&mut self, names: &[String]) {
+        self.last_sent.retain(|name, _| names.contains(name));
+    }
+
+    fn cooldown_allows(&self, transition: &HealthTransition, cooldown: Duration) -> bool {
+        match self.last_sent.get(&transition.name) {
+            None => true,
+            Some((sent, kind)) => {
+                sent.elapsed() >= cooldown
+                    || (transition.kind == "recovered" && kind != "recovered")
+            }
+        }
+    }
+
+    async fn dispatch(&mut self, app: &AppHandle, transitions: &[HealthTransition]) {
+        if transitions.is_empty() {
+            return;
+        }
+        if let Ok(mut errors) = self.errors.lock() {
+            errors.clear();
+        }
+        if let Err(err) = app.emit("health-transitions", transitions.to_vec()) {
+            self.report(format!("Failed to emit health transitions: {}", err));
+        }
+
+        let config = AlertConfig::from_env();
+        let quiet = config.in_quiet_hours();
+
+        for transition in transitions {
+            if !self.cooldown_allows(transition, config.cooldown) {
+                continue;
+            }
+
+            let (title, body) = health_alert_message(transition);
+            let mut sent = false;
+
+            if config.desktop && !quiet {
+                match app.notification().builder().title(&title).body(&body).show() {
+                    Ok(()) => sent = true,
+                    Err(err) => self.report(format!("Failed to show health notification: {}", err)),
+                }
+            }
+
+            // Spawned so a slow webhook does not hold up the next health round
+            for webhook in config
+                .webhooks
+                .iter()
+                .filter(|webhook| !(quiet && webhook.respect_quiet_hours))
+            {
+                let client = self.client.clone();
+                let url = webhook.url.clone();
+                let payload = alert_webhook_payload(webhook, transition, &title, &body);
+                let errors = self.errors.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(err) = post_alert_webhook(&client, &url, &payload).await {
+                        if let Ok(mut errors) = errors.lock() {
+                            errors.push(err);
+                        }
+                    }
+                });
+                sent = true;
+            }
+
+            // Quiet hours that silenced every channel do not start a cooldown
+            if sent {
+                self.last_sent.insert(
+                    transition.name.clone(),
+                    (std::time::Instant::now(), transition.kind.clone()),
+                );
+            }
+        }
+    }
+}
+
+fn alert_webhook_payload(
+    webhook: &AlertWebhook,
+    transition: &HealthTransition,
+    title: &str,
+    body: &str,
+) -> serde_json::Value {
+    match webhook.format.as_str() {
+        "slack" => serde_json::json!({ "text": format!("*{}*\n{}", title, body) }),
+        _ => serde_json::to_value(transition).unwrap_or_default(),
+    }
+}
+
+async fn post_alert_webhook(
+    client: &reqwest::Client,
+    url: &str,
+    payload: &serde_json::Value,
+) -> Result<(), String> {
+    let response = client
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to call health alert webhook {}: {}", url, e))?;
 
-            JiraTicket {
-                key,
-                summary,
-                status,
-                assignee,
-                url,
-            }
-        })
-        .collect();
+    if !response.status().is_success() {
+        return Err(format!(
+            "Health alert webhook {} returned {}",
+            url,
+            response.status()
+        ));
+    }
+    Ok(())
+}
 
-    Ok(tickets)
+fn health_alert_message(transition: &HealthTransition) -> (String, String) {
+    let title = match transition.kind.as_str() {
+        "down" => format!("{} is down", transition.name),
+        "recovered" => format!("{} recovered", transition.name),
+        _ => format!("{} is flapping", transition.name),
+    };
+    let body = match (&transition.error, transition.kind.as_str()) {
+        (Some(error), "down") | (Some(error), "flapping") => error.clone(),
+        _ => format!("{} is {}", transition.url, transition.to_state),
+    };
+    (title, body)
 }
 
-async fn collect_service_health() -> Vec<ServiceHealth> {
-    let services = [
-        ("Trisolaris", "https://app.florianraith.com/up"),
-        ("Spliit", "https://spliit.florianraith.com/api/health"),
-        ("Partnerportal (Dev)", "https://dev-portal.zewotherm.com/up"),
-        ("Partnerportal (Prod)", "https://portal.zewotherm.com/up"),
-    ];
+// Runs all checks concurrently and writes each result to the snapshot as soon
+// as it completes, so one hanging endpoint does not hold back the others
+async fn collect_service_health(
+    snapshot: &RwLock<AppSnapshot>,
+    history: &Mutex<HealthHistory>,
+    tracker: &mut HealthTracker,
+) -> (Vec<HealthTransition>, Vec<String>) {
+    let mut errors = Vec::new();
+    let mut services = health_check_configs().unwrap_or_else(|err| {
+        errors.push(err);
+        Vec::new()
+    });
+    // Resolve credentials once per round instead of on every attempt and retry
+    for service in &mut services {
+        if let Some(auth) = &service.auth {
+            service.auth_secret = Some(auth.secret().resolve().await);
+        }
+    }
+    let names: Vec<String> = services.iter().map(|s| s.name.clone()).collect();
+    let policies: HashMap<String, HealthPolicy> = services
+        .iter()
+        .map(|s| (s.name.clone(), s.policy.clone()))
+        .collect();
+    let concurrency = env::var("HEALTH_CHECK_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(4)
+        .max(1);
 
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(8))
-        .build()
-        .unwrap_or_else(|_| reqwest::Client::new());
+    let client = reqwest::Client::new();
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency));
+    let mut checks = tokio::task::JoinSet::new();
+    let mut transitions = Vec::new();
 
-    let mut results = Vec::with_capacity(services.len());
+    for service in services {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        checks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            check_service_with_retries(&client, &service).await
+        });
+    }
 
-    for (name, url) in services {
-        let checked_at_ms = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_millis())
-            .unwrap_or(0);
-        let started = std::time::Instant::now();
-        let response = client.get(url).send().await;
-
-        match response {
-            Ok(resp) => {
-                let status = resp.status();
-                results.push(ServiceHealth {
-                    name: name.to_string(),
-                    url: url.to_string(),
-                    is_up: status.is_success(),
-                    status_code: Some(status.as_u16()),
-                    latency_ms: Some(started.elapsed().as_millis()),
-                    checked_at_ms,
-                    error: None,
-                });
-            }
-            Err(err) => {
-                results.push(ServiceHealth {
-                    name: name.to_string(),
-                    url: url.to_string(),
-                    is_up: false,
-                    status_code: None,
-                    latency_ms: Some(started.elapsed().as_millis()),
-                    checked_at_ms,
-                    error: Some(err.to_string()),
-                });
+    while let Some(result) = checks.join_next().await {
+        let Ok(mut health) = result else {
+            continue;
+        };
+        let smoothed_state = match policies.get(&health.name) {
+            Some(policy) => tracker.apply(policy, &mut health),
+            None => health.state.clone(),
+        };
+        // Flapping is a label on top of the smoothed state, uptime needs the latter
+        if let Ok(mut history) = history.lock() {
+            history.record(&health, &smoothed_state);
+        }
+        if let Ok(mut state) = snapshot.write() {
+            match state.health.iter_mut().find(|h| h.name == health.name) {
+                Some(existing) => {
+                    if let Some(kind) = health_transition_kind(&existing.state, &health.state) {
+                        transitions.push(HealthTransition {
+                            name: health.name.clone(),
+                            url: health.url.clone(),
+                            kind: kind.to_string(),
+                            from_state: existing.state.clone(),
+                            to_state: health.state.clone(),
+                            error: health.error.clone(),
+                            changed_at_ms: health.checked_at_ms,
+                        });
+                    }
+                    *existing = health;
+                }
+                None => state.health.push(health),
             }
+            // Keep the configured order and drop services removed from the config
+            state.health.retain(|h| names.contains(&h.name));
+            state
+                .health
+                .sort_by_key(|h| names.iter().position(|name| *name == h.name));
+        }
+    }
+
+    // Services removed from the config, or all of them when it is invalid
+    if let Ok(mut state) = snapshot.write() {
+        state.health.retain(|h| names.contains(&h.name));
+    }
+    tracker.retain(&names);
+
+    // Written once per round, on a blocking thread and outside the lock
+    let write = history.lock().ok().and_then(|mut history| history.pending_write());
+    if let Some(write) = write {
+        let result = tokio::task::spawn_blocking(move || write.apply())
+            .await
+            .unwrap_or_else(|e| Err(format!("Failed to save health history: {}", e)));
+        if let Ok(mut history) = history.lock() {
+            history.write_error = result.err();
         }
     }
+    if let Some(err) = history.lock().ok().and_then(|history| history.write_error.clone()) {
+        errors.push(err);
+    }
 
-    results
+    (transitions, errors)
 }
 
 fn format_age_from_first_seen(first_seen: &str) -> String {
@@ -562,17 +4793,58 @@ fn format_age_from_first_seen(first_seen: &str) -> String {
     "n/a".to_string()
 }
 
-async fn collect_sentry_issues() -> Result<Vec<SentryIssue>, String> {
-    let token = env::var("SENTRY_AUTH_TOKEN")
-        .map_err(|_| "SENTRY_AUTH_TOKEN environment variable not set".to_string())?;
+fn sentry_sources() -> Result<Vec<SentrySource>, String> {
+    // SENTRY_SOURCES holds a JSON array of {"organization", "base_url", "auth_token",
+    // "projects", "environments", "query", "sort", "stats_period", "limit"} objects
+    if let Ok(raw) = env::var("SENTRY_SOURCES") {
+        if !raw.trim().is_empty() {
+            let sources: Vec<SentrySource> = serde_json::from_str(&raw)
+                .map_err(|e| format!("Failed to parse SENTRY_SOURCES: {}", e))?;
+            if sources.is_empty() {
+                return Err("SENTRY_SOURCES does not contain any sources".to_string());
+            }
+            return Ok(sources);
+        }
+    }
 
-    let url = "https://sentry.io/api/0/organizations/zw-systems-gmbh/issues/?project=4509966802485248&statsPeriod=90d&sort=date&limit=15&query=is:unresolved";
+    Ok(vec![SentrySource {
+        organization: "zw-systems-gmbh".to_string(),
+        base_url: default_sentry_base_url(),
+        auth_token: None,
+        projects: vec!["4509966802485248".to_string()],
+        environments: Vec::new(),
+        query: default_sentry_query(),
+        sort: default_sentry_sort(),
+        stats_period: default_sentry_stats_period(),
+        limit: default_sentry_limit(),
+    }])
+}
 
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(12))
-        .build()
-        .unwrap_or_else(|_| reqwest::Client::new());
+// The next page of a Sentry list endpoint, from the `Link` header entry with
+// rel="next" as long as Sentry reports results="true" for it
+fn sentry_next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|entry| {
+        let mut parts = entry.split(';').map(str::trim);
+        let url = parts.next()?.strip_prefix('<')?.strip_suffix('>')?;
+        let params: Vec<&str> = parts.collect();
+        (params.contains(&"rel=\"next\"") && params.contains(&"results=\"true\""))
+            .then(|| url.to_string())
+    })
+}
 
+async fn fetch_sentry_json(
+    client: &reqwest::Client,
+    token: &str,
+    url: &str,
+) -> Result<serde_json::Value, String> {
+    fetch_sentry_page(client, token, url).await.map(|(json, _)| json)
+}
+
+async fn fetch_sentry_page(
+    client: &reqwest::Client,
+    token: &str,
+    url: &str,
+) -> Result<(serde_json::Value, Option<String>), String> {
     let response = client
         .get(url)
         .header("Authorization", format!("Bearer {}", token))
@@ -587,140 +4859,729 @@ async fn collect_sentry_issues() -> Result<Vec<SentryIssue>, String> {
         return Err(format!("Sentry API error ({}): {}", status, body));
     }
 
-    let json: serde_json::Value = response
+    let next_url = response
+        .headers()
+        .get(reqwest::header::LINK)
+        .and_then(|link| link.to_str().ok())
+        .and_then(sentry_next_page_url);
+    let json = response
         .json()
         .await
         .map_err(|e| format!("Failed to parse Sentry response: {}", e))?;
+    Ok((json, next_url))
+}
+
+// The issues endpoint filters by numeric project id, so slugs are looked up first
+async fn resolve_sentry_project_ids(
+    client: &reqwest::Client,
+    token: &str,
+    source: &SentrySource,
+) -> Result<Vec<String>, String> {
+    if source.projects.iter().all(|p| p.chars().all(|c| c.is_ascii_digit())) {
+        return Ok(source.projects.clone());
+    }
+
+    let is_id = |project: &String| project.chars().all(|c| c.is_ascii_digit());
+    let mut ids: HashMap<String, String> = HashMap::new();
+    let mut next_url = Some(format!(
+        "{}/organizations/{}/projects/",
+        source.api_url(),
+        urlencoding::encode(&source.organization)
+    ));
+    while let Some(url) = next_url.take() {
+        let (json, next) = fetch_sentry_page(client, token, &url).await?;
+        let projects = json
+            .as_array()
+            .ok_or("Invalid Sentry response format: expected array")?;
+        for project in projects {
+            if let (Some(slug), Some(id)) = (project["slug"].as_str(), project["id"].as_str()) {
+                if source.projects.iter().any(|p| p == slug) {
+                    ids.insert(slug.to_string(), id.to_string());
+                }
+            }
+        }
+
+        // Large organizations span many pages, stop once every slug is known
+        if source.projects.iter().all(|p| is_id(p) || ids.contains_key(p)) {
+            break;
+        }
+        next_url = next;
+    }
+
+    source
+        .projects
+        .iter()
+        .map(|project| {
+            if is_id(project) {
+                return Ok(project.clone());
+            }
+            ids.get(project)
+                .cloned()
+                .ok_or_else(|| {
+                    format!("Sentry project '{}' not found in '{}'", project, source.organization)
+                })
+        })
+        .collect()
+}
+
+fn parse_sentry_issue(
+    source_id: usize,
+    organization: &str,
+    issue: &serde_json::Value,
+) -> SentryIssue {
+    let title = issue["title"]
+        .as_str()
+        .or_else(|| issue["metadata"]["title"].as_str())
+        .unwrap_or("Unknown issue")
+        .to_string();
+
+    let last_seen = issue["lastSeen"]
+        .as_str()
+        .unwrap_or("n/a")
+        .to_string();
+    let first_seen = issue["firstSeen"]
+        .as_str()
+        .unwrap_or("n/a")
+        .to_string();
+
+    let events = issue["count"]
+        .as_str()
+        .and_then(|v| v.parse::<u64>().ok())
+        .or_else(|| issue["count"].as_u64())
+        .unwrap_or(0);
+
+    let users = issue["userCount"].as_u64().unwrap_or(0);
+    let is_bot = issue["tags"]
+        .as_array()
+        .map(|tags| {
+            tags.iter().any(|tag| {
+                tag["key"].as_str() == Some("browser")
+                    && tag["value"]
+                        .as_str()
+                        .map(|value| value.contains("Python"))
+                        .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false);
+    let url = issue["permalink"].as_str().unwrap_or("").to_string();
+
+    SentryIssue {
+        id: issue["id"].as_str().unwrap_or("").to_string(),
+        source_id,
+        title,
+        organization: organization.to_string(),
+        project: issue["project"]["slug"].as_str().unwrap_or("").to_string(),
+        status: issue["status"].as_str().unwrap_or("unresolved").to_string(),
+        assigned_to: issue["assignedTo"]["name"].as_str().map(str::to_string),
+        is_bookmarked: issue["isBookmarked"].as_bool().unwrap_or(false),
+        age: format_age_from_first_seen(&first_seen),
+        last_seen,
+        first_seen,
+        events,
+        users,
+        is_bot,
+        url,
+    }
+}
+
+async fn collect_sentry_source(
+    client: &reqwest::Client,
+    source_id: usize,
+    source: &SentrySource,
+) -> Result<Vec<SentryIssue>, String> {
+    let token = &source.token().await?;
+    let mut params = vec![
+        ("statsPeriod".to_string(), source.stats_period.clone()),
+        ("sort".to_string(), source.sort.clone()),
+        ("limit".to_string(), source.limit.to_string()),
+        ("query".to_string(), source.query.clone()),
+    ];
+    for project in resolve_sentry_project_ids(client, token, source).await? {
+        params.push(("project".to_string(), project));
+    }
+    for environment in &source.environments {
+        params.push(("environment".to_string(), environment.clone()));
+    }
+
+    let query = params
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+    let url = format!(
+        "{}/organizations/{}/issues/?{}",
+        source.api_url(),
+        urlencoding::encode(&source.organization),
+        query
+    );
 
+    let json = fetch_sentry_json(client, token, &url).await?;
     let issues = json
         .as_array()
         .ok_or("Invalid Sentry response format: expected array")?;
 
-    let mapped = issues
+    Ok(issues
         .iter()
-        .map(|issue| {
-            let title = issue["title"]
-                .as_str()
-                .or_else(|| issue["metadata"]["title"].as_str())
-                .unwrap_or("Unknown issue")
-                .to_string();
+        .map(|issue| parse_sentry_issue(source_id, &source.organization, issue))
+        .collect())
+}
 
-            let last_seen = issue["lastSeen"]
-                .as_str()
-                .unwrap_or("n/a")
-                .to_string();
-            let first_seen = issue["firstSeen"]
-                .as_str()
-                .unwrap_or("n/a")
-                .to_string();
+// Each source comes back sorted by Sentry; merging keeps that order where the sort
+// key is available locally and otherwise interleaves the sources rank by rank
+fn merge_sentry_issues(sources: Vec<(String, Vec<SentryIssue>)>) -> Vec<SentryIssue> {
+    if sources.len() == 1 {
+        return sources.into_iter().flat_map(|(_, issues)| issues).collect();
+    }
 
-            let events = issue["count"]
-                .as_str()
-                .and_then(|v| v.parse::<u64>().ok())
-                .or_else(|| issue["count"].as_u64())
-                .unwrap_or(0);
-
-            let users = issue["userCount"].as_u64().unwrap_or(0);
-            let is_bot = issue["tags"]
-                .as_array()
-                .map(|tags| {
-                    tags.iter().any(|tag| {
-                        tag["key"].as_str() == Some("browser")
-                            && tag["value"]
-                                .as_str()
-                                .map(|value| value.contains("Python"))
-                                .unwrap_or(false)
-                    })
-                })
-                .unwrap_or(false);
-            let url = issue["permalink"].as_str().unwrap_or("").to_string();
+    let shared_sort = sources
+        .first()
+        .map(|(sort, _)| sort.clone())
+        .filter(|sort| sources.iter().all(|(other, _)| other == sort));
+    let mut ranked: Vec<(usize, SentryIssue)> = sources
+        .into_iter()
+        .flat_map(|(_, issues)| issues.into_iter().enumerate())
+        .collect();
+    ranked.sort_by(|(rank_a, a), (rank_b, b)| {
+        let by_key = match shared_sort.as_deref() {
+            Some("date") => b.last_seen.cmp(&a.last_seen),
+            Some("new") => b.first_seen.cmp(&a.first_seen),
+            Some("freq") => b.events.cmp(&a.events),
+            Some("user") => b.users.cmp(&a.users),
+            // "priority" and "trends" are scored by Sentry and not part of the response
+            _ => std::cmp::Ordering::Equal,
+        };
+        by_key.then(rank_a.cmp(rank_b))
+    });
+    ranked.into_iter().map(|(_, issue)| issue).collect()
+}
+
+async fn collect_sentry_issues() -> Result<SentryIssueList, String> {
+    collect_sentry_issue_list(&sentry_sources()?).await
+}
+
+async fn collect_sentry_issue_list(sources: &[SentrySource]) -> Result<SentryIssueList, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(12))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+
+    let mut loaded = Vec::new();
+    let mut errors = Vec::new();
+    for (source_id, source) in sources.iter().enumerate() {
+        match collect_sentry_source(&client, source_id, source).await {
+            Ok(issues) => loaded.push((source.sort.clone(), issues)),
+            Err(err) => errors.push(format!("{}: {}", source.organization, err)),
+        }
+    }
+
+    // Only fail when nothing could be loaded, a single broken source is reported alongside
+    if loaded.is_empty() {
+        return Err(errors.join("; "));
+    }
+
+    Ok(SentryIssueList {
+        issues: merge_sentry_issues(loaded),
+        errors,
+    })
+}
+
+async fn update_sentry_issue(
+    snapshot: &RwLock<AppSnapshot>,
+    source_id: usize,
+    issue_id: &str,
+    body: serde_json::Value,
+) -> Result<(), String> {
+    let source = sentry_sources()?
+        .into_iter()
+        .nth(source_id)
+        .ok_or_else(|| format!("Sentry source {} is not configured", source_id))?;
+    let token = source.token().await?;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(12))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+
+    let response = client
+        .put(format!(
+            "{}/organizations/{}/issues/{}/",
+            source.api_url(),
+            urlencoding::encode(&source.organization),
+            urlencoding::encode(issue_id)
+        ))
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/json")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to update Sentry issue: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Sentry API error ({}): {}", status, body));
+    }
+
+    let updated: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Sentry response: {}", e))?;
+
+    // Apply the change right away instead of waiting for the next poll
+    if let Ok(mut state) = snapshot.write() {
+        if let Ok(list) = state.sentry.as_mut() {
+            let issues = &mut list.issues;
+            let is_target = |issue: &SentryIssue| issue.source_id == source_id && issue.id == issue_id;
+            if let Some(issue) = issues.iter_mut().find(|issue| is_target(issue)) {
+                if let Some(status) = updated["status"].as_str() {
+                    issue.status = status.to_string();
+                }
+                if updated.get("assignedTo").is_some() {
+                    issue.assigned_to = updated["assignedTo"]["name"].as_str().map(str::to_string);
+                }
+                if let Some(is_bookmarked) = updated["isBookmarked"].as_bool() {
+                    issue.is_bookmarked = is_bookmarked;
+                }
+            }
 
-            SentryIssue {
-                title,
-                age: format_age_from_first_seen(&first_seen),
-                last_seen,
-                first_seen,
-                events,
-                users,
-                is_bot,
-                url,
+            // Drop issues the source query would no longer return
+            if source.query.contains("is:unresolved") {
+                issues.retain(|issue| !is_target(issue) || issue.status == "unresolved");
             }
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn resolve_sentry_issue(
+    state: State<'_, AppState>,
+    source_id: usize,
+    issue_id: String,
+) -> Result<(), String> {
+    update_sentry_issue(
+        &state.snapshot,
+        source_id,
+        &issue_id,
+        serde_json::json!({ "status": "resolved" }),
+    )
+    .await
+}
+
+// Ignores until the issue occurs `count` more times (optionally within
+// `window_minutes`), for `duration_minutes`, or forever when neither is set
+#[tauri::command]
+async fn ignore_sentry_issue(
+    state: State<'_, AppState>,
+    source_id: usize,
+    issue_id: String,
+    duration_minutes: Option<u64>,
+    count: Option<u64>,
+    window_minutes: Option<u64>,
+) -> Result<(), String> {
+    let mut status_details = serde_json::Map::new();
+    if let Some(duration) = duration_minutes {
+        status_details.insert("ignoreDuration".to_string(), duration.into());
+    }
+    if let Some(count) = count {
+        status_details.insert("ignoreCount".to_string(), count.into());
+        if let Some(window) = window_minutes {
+            status_details.insert("ignoreWindow".to_string(), window.into());
+        }
+    }
+
+    update_sentry_issue(
+        &state.snapshot,
+        source_id,
+        &issue_id,
+        serde_json::json!({ "status": "ignored", "statusDetails": status_details }),
+    )
+    .await
+}
+
+// `assignee` is a username, email, "user:<id>" or "team:<id>"; empty unassigns
+#[tauri::command]
+async fn assign_sentry_issue(
+    state: State<'_, AppState>,
+    source_id: usize,
+    issue_id: String,
+    assignee: String,
+) -> Result<(), String> {
+    update_sentry_issue(
+        &state.snapshot,
+        source_id,
+        &issue_id,
+        serde_json::json!({ "assignedTo": assignee.trim() }),
+    )
+    .await
+}
+
+#[tauri::command]
+async fn bookmark_sentry_issue(
+    state: State<'_, AppState>,
+    source_id: usize,
+    issue_id: String,
+    bookmarked: bool,
+) -> Result<(), String> {
+    update_sentry_issue(
+        &state.snapshot,
+        source_id,
+        &issue_id,
+        serde_json::json!({ "isBookmarked": bookmarked }),
+    )
+    .await
+}
+
+#[tauri::command]
+fn get_ram_usage(state: State<'_, AppState>) -> RamUsage {
+    state
+        .snapshot
+        .read()
+        .expect("failed to lock state")
+        .ram
+        .clone()
+}
+
+async fn apply_spotify_action(
+    state: State<'_, AppState>,
+    action: SpotifyAction,
+) -> Result<SpotifyTrack, String> {
+    let mut spotify = match spotify_backend() {
+        SpotifyBackend::Local => tauri::async_runtime::spawn_blocking(move || {
+            run_spotify_action(action).map(|_| collect_spotify_track())
         })
-        .collect();
+        .await
+        .map_err(|e| format!("Spotify action failed: {}", e))??,
+        SpotifyBackend::WebApi => {
+            let config = SpotifyWebConfig::from_env()?;
+            run_spotify_web_action(&state.spotify_web, &config, action).await?;
+            collect_spotify_web_track(&state.spotify_web, &config).await
+        }
+    };
+
+    state.artwork_cache.enrich(&mut spotify).await;
+
+    record_spotify_play(&state.spotify_history, &spotify);
+
+    // Refresh the snapshot right away instead of waiting for the next poll
+    if let Ok(mut snapshot) = state.snapshot.write() {
+        snapshot.spotify = spotify.clone();
+    }
+
+    spotify
+}
+
+#[tauri::command]
+async fn spotify_web_login(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let config = SpotifyWebConfig::from_env()?;
+    let (verifier, challenge) = generate_pkce_pair();
+    let csrf_state = random_token(16);
+    let redirect_uri = config.redirect_uri();
+
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", config.redirect_port))
+        .await
+        .map_err(|e| format!("Failed to listen for Spotify callback: {}", e))?;
+
+    let authorize_url = format!(
+        "{}/authorize?response_type=code&client_id={}&scope={}&redirect_uri={}&code_challenge_method=S256&code_challenge={}&state={}",
+        config.accounts_base_url,
+        urlencoding::encode(&config.client_id),
+        urlencoding::encode(SPOTIFY_WEB_SCOPES),
+        urlencoding::encode(&redirect_uri),
+        challenge,
+        csrf_state
+    );
+    app.opener()
+        .open_url(authorize_url, None::<&str>)
+        .map_err(|e| format!("Failed to open Spotify login page: {}", e))?;
+
+    let code = tokio::time::timeout(
+        Duration::from_secs(180),
+        wait_for_spotify_callback(&listener, &csrf_state),
+    )
+    .await
+    .map_err(|_| "Timed out waiting for Spotify login".to_string())??;
+
+    let (_, refresh_token) = exchange_spotify_code(&state.spotify_web, &config, &code, &verifier).await?;
+    let refresh_token =
+        refresh_token.ok_or("Spotify login failed: no refresh token in the token response")?;
+
+    store_spotify_refresh_token(refresh_token).await
+}
+
+#[tauri::command]
+async fn spotify_play_pause(state: State<'_, AppState>) -> Result<SpotifyTrack, String> {
+    apply_spotify_action(state, SpotifyAction::PlayPause).await
+}
+
+#[tauri::command]
+async fn spotify_next(state: State<'_, AppState>) -> Result<SpotifyTrack, String> {
+    apply_spotify_action(state, SpotifyAction::Next).await
+}
+
+#[tauri::command]
+async fn spotify_previous(state: State<'_, AppState>) -> Result<SpotifyTrack, String> {
+    apply_spotify_action(state, SpotifyAction::Previous).await
+}
+
+#[tauri::command]
+async fn spotify_seek(
+    state: State<'_, AppState>,
+    position_ms: u64,
+) -> Result<SpotifyTrack, String> {
+    apply_spotify_action(state, SpotifyAction::Seek(position_ms)).await
+}
+
+#[tauri::command]
+async fn spotify_set_volume(
+    state: State<'_, AppState>,
+    volume: u8,
+) -> Result<SpotifyTrack, String> {
+    apply_spotify_action(state, SpotifyAction::Volume(volume.min(100))).await
+}
+
+#[tauri::command]
+fn get_cpu_usage(state: State<'_, AppState>) -> CpuUsage {
+    state
+        .snapshot
+        .read()
+        .expect("failed to lock state")
+        .cpu
+        .clone()
+}
+
+#[tauri::command]
+fn get_docker_containers(state: State<'_, AppState>) -> Result<Vec<DockerContainer>, String> {
+    state
+        .snapshot
+        .read()
+        .expect("failed to lock state")
+        .docker
+        .clone()
+}
+
+#[tauri::command]
+fn get_spotify_track(state: State<'_, AppState>) -> Result<SpotifyTrack, String> {
+    state
+        .snapshot
+        .read()
+        .expect("failed to lock state")
+        .spotify
+        .clone()
+}
+
+#[tauri::command]
+fn get_spotify_history(state: State<'_, AppState>, limit: Option<usize>) -> Vec<PlayedTrack> {
+    state
+        .spotify_history
+        .lock()
+        .expect("failed to lock state")
+        .recent(limit.unwrap_or(50))
+}
+
+#[tauri::command]
+fn get_spotify_history_error(state: State<'_, AppState>) -> Option<String> {
+    state
+        .spotify_history
+        .lock()
+        .expect("failed to lock state")
+        .save_error
+        .clone()
+}
+
+#[tauri::command]
+fn get_jira_queries() -> Result<Vec<JiraQuery>, String> {
+    jira_queries()
+}
+
+#[tauri::command]
+fn get_jira_tickets(
+    state: State<'_, AppState>,
+    query_id: Option<String>,
+) -> Result<JiraTicketList, String> {
+    let queries = jira_queries()?;
+    let query_id = match query_id {
+        Some(query_id) => query_id,
+        None => queries
+            .first()
+            .map(|query| query.id.clone())
+            .unwrap_or_else(|| DEFAULT_JIRA_QUERY_ID.to_string()),
+    };
+
+    if !queries.iter().any(|query| query.id == query_id) {
+        return Err(format!("Unknown Jira query: {}", query_id));
+    }
 
-    Ok(mapped)
+    state
+        .snapshot
+        .read()
+        .expect("failed to lock state")
+        .jira
+        .get(&query_id)
+        .cloned()
+        .unwrap_or_else(|| Err("Loading Jira tickets...".to_string()))
 }
 
 #[tauri::command]
-fn get_ram_usage(state: State<'_, AppState>) -> RamUsage {
+fn get_forge_overview(state: State<'_, AppState>) -> Result<ForgeOverview, String> {
     state
         .snapshot
         .read()
         .expect("failed to lock state")
-        .ram
+        .forge
         .clone()
 }
 
 #[tauri::command]
-fn get_cpu_usage(state: State<'_, AppState>) -> CpuUsage {
+fn get_pipeline_statuses(state: State<'_, AppState>) -> Result<Vec<PipelineStatus>, String> {
     state
         .snapshot
         .read()
         .expect("failed to lock state")
-        .cpu
+        .pipelines
         .clone()
 }
 
 #[tauri::command]
-fn get_docker_containers(state: State<'_, AppState>) -> Result<Vec<DockerContainer>, String> {
+fn get_git_repositories(state: State<'_, AppState>) -> Result<Vec<GitRepoStatus>, String> {
     state
         .snapshot
         .read()
         .expect("failed to lock state")
-        .docker
+        .git_repos
         .clone()
 }
 
 #[tauri::command]
-fn get_spotify_track(state: State<'_, AppState>) -> Result<SpotifyTrack, String> {
+fn get_service_health(state: State<'_, AppState>) -> Vec<ServiceHealth> {
     state
         .snapshot
         .read()
         .expect("failed to lock state")
-        .spotify
+        .health
         .clone()
 }
 
 #[tauri::command]
-fn get_jira_tickets(state: State<'_, AppState>) -> Result<Vec<JiraTicket>, String> {
+fn get_health_errors(state: State<'_, AppState>) -> Vec<String> {
     state
         .snapshot
         .read()
         .expect("failed to lock state")
-        .jira
+        .health_errors
         .clone()
 }
 
 #[tauri::command]
-fn get_service_health(state: State<'_, AppState>) -> Vec<ServiceHealth> {
+fn get_service_uptime(
+    state: State<'_, AppState>,
+    name: String,
+    window_hours: Option<u64>,
+    buckets: Option<usize>,
+) -> ServiceUptime {
+    state
+        .health_history
+        .lock()
+        .expect("failed to lock state")
+        .uptime(&name, u128::from(window_hours.unwrap_or(24)), buckets.unwrap_or(48))
+}
+
+#[tauri::command]
+fn get_sentry_issues(state: State<'_, AppState>) -> Result<SentryIssueList, String> {
     state
         .snapshot
         .read()
         .expect("failed to lock state")
-        .health
+        .sentry
         .clone()
 }
 
 #[tauri::command]
-fn get_sentry_issues(state: State<'_, AppState>) -> Result<Vec<SentryIssue>, String> {
+fn get_jira_sprint(state: State<'_, AppState>) -> Result<JiraSprint, String> {
     state
         .snapshot
         .read()
         .expect("failed to lock state")
-        .sentry
+        .jira_sprint
         .clone()
 }
 
+#[tauri::command]
+async fn get_jira_transitions(
+    state: State<'_, AppState>,
+    issue_key: String,
+) -> Result<Vec<JiraTransition>, String> {
+    fetch_jira_transitions(&state.jira_client, &issue_key).await
+}
+
+#[tauri::command]
+async fn transition_jira_ticket(
+    state: State<'_, AppState>,
+    issue_key: String,
+    transition_id: String,
+) -> Result<(), String> {
+    // The optimistic status comes from Jira itself rather than the caller
+    let transition = fetch_jira_transitions(&state.jira_client, &issue_key)
+        .await?
+        .into_iter()
+        .find(|transition| transition.id == transition_id)
+        .ok_or_else(|| {
+            format!(
+                "Transition {} is not available for {}",
+                transition_id, issue_key
+            )
+        })?;
+
+    let previous = patch_jira_ticket(&state.snapshot, &issue_key, |ticket| {
+        ticket.status = transition.to_status.clone();
+        ticket.status_category = transition.to_status_category.clone();
+    });
+
+    let result = perform_jira_transition(&state.jira_client, &issue_key, &transition.id).await;
+    if result.is_err() {
+        restore_jira_tickets(&state.snapshot, previous, |ticket| {
+            ticket.status == transition.to_status
+                && ticket.status_category == transition.to_status_category
+        });
+    }
+    result
+}
+
+#[tauri::command]
+async fn assign_jira_ticket_to_me(
+    state: State<'_, AppState>,
+    issue_key: String,
+) -> Result<(), String> {
+    let config = JiraConfig::from_env()?;
+    let user = state.jira_client.current_user(&config).await?;
+
+    let previous = patch_jira_ticket(&state.snapshot, &issue_key, |ticket| {
+        ticket.assignee = user.display_name.clone();
+        ticket.is_mine = true;
+    });
+
+    let result = perform_jira_assign(&state.jira_client, &issue_key, &user.account_id).await;
+    if result.is_err() {
+        restore_jira_tickets(&state.snapshot, previous, |ticket| {
+            ticket.is_mine && ticket.assignee == user.display_name
+        });
+    }
+    result
+}
+
+#[tauri::command]
+async fn add_jira_comment(
+    state: State<'_, AppState>,
+    issue_key: String,
+    body: String,
+) -> Result<(), String> {
+    if body.trim().is_empty() {
+        return Err("Comment must not be empty".to_string());
+    }
+    perform_jira_comment(&state.jira_client, &issue_key, &body).await
+}
+
 fn start_background_pollers(app: &AppHandle) {
     let snapshot_for_ram = app.state::<AppState>().snapshot.clone();
     tauri::async_runtime::spawn(async move {
@@ -762,10 +5623,24 @@ fn start_background_pollers(app: &AppHandle) {
     });
 
     let snapshot_for_spotify = app.state::<AppState>().snapshot.clone();
+    let spotify_web = app.state::<AppState>().spotify_web.clone();
+    let spotify_history = app.state::<AppState>().spotify_history.clone();
+    let artwork_cache = app.state::<AppState>().artwork_cache.clone();
     tauri::async_runtime::spawn(async move {
         tokio::time::sleep(Duration::from_millis(1250)).await;
         loop {
-            if let Ok(spotify) = tauri::async_runtime::spawn_blocking(collect_spotify_track).await {
+            let spotify = match spotify_backend() {
+                SpotifyBackend::Local => tauri::async_runtime::spawn_blocking(collect_spotify_track)
+                    .await
+                    .ok(),
+                SpotifyBackend::WebApi => Some(match SpotifyWebConfig::from_env() {
+                    Ok(config) => collect_spotify_web_track(&spotify_web, &config).await,
+                    Err(err) => Err(err),
+                }),
+            };
+            if let Some(mut spotify) = spotify {
+                artwork_cache.enrich(&mut spotify).await;
+                record_spotify_play(&spotify_history, &spotify);
                 if let Ok(mut state) = snapshot_for_spotify.write() {
                     state.spotify = spotify;
                 }
@@ -775,24 +5650,174 @@ fn start_background_pollers(app: &AppHandle) {
     });
 
     let snapshot_for_jira = app.state::<AppState>().snapshot.clone();
+    let jira_client = app.state::<AppState>().jira_client.clone();
+    let app_for_jira = app.clone();
     tauri::async_runtime::spawn(async move {
         tokio::time::sleep(Duration::from_millis(1700)).await;
+        let mut seen_keys: HashMap<String, HashSet<String>> = HashMap::new();
+        loop {
+            // Each query is collected on its own so one failing JQL does not
+            // hide the results of the others
+            if let Ok(queries) = jira_queries() {
+                // Drop results for queries that were removed from JIRA_QUERIES
+                if let Ok(mut state) = snapshot_for_jira.write() {
+                    state
+                        .jira
+                        .retain(|id, _| queries.iter().any(|query| &query.id == id));
+                }
+                seen_keys.retain(|id, _| queries.iter().any(|query| &query.id == id));
+
+                for query in queries {
+                    let jira = collect_jira_tickets(&jira_client, &query.jql).await;
+
+                    // Only diff against a successful previous result so the
+                    // first load and recovered errors do not flood notifications
+                    let previous = if query.notify {
+                        snapshot_for_jira
+                            .read()
+                            .ok()
+                            .and_then(|state| state.jira.get(&query.id).cloned())
+                            .and_then(Result::ok)
+                    } else {
+                        None
+                    };
+                    let seen = seen_keys.entry(query.id.clone()).or_default();
+                    let changes = match (&previous, &jira) {
+                        (Some(previous), Ok(current)) => {
+                            diff_jira_tickets(&query, previous, current, seen)
+                        }
+                        _ => Vec::new(),
+                    };
+                    // Replaced every round so the set stays as small as the last two results
+                    if let Ok(current) = &jira {
+                        *seen = current
+                            .tickets
+                            .iter()
+                            .chain(previous.iter().flat_map(|previous| previous.tickets.iter()))
+                            .map(|ticket| ticket.key.clone())
+                            .collect();
+                    }
+
+                    let notify_errors = if changes.is_empty() {
+                        Vec::new()
+                    } else {
+                        notify_jira_changes(&app_for_jira, &changes)
+                    };
+                    let jira = jira.map(|mut list| {
+                        list.errors = notify_errors;
+                        list
+                    });
+
+                    if let Ok(mut state) = snapshot_for_jira.write() {
+                        state.jira.insert(query.id, jira);
+                    }
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(30000)).await;
+        }
+    });
+
+    let snapshot_for_jira_sprint = app.state::<AppState>().snapshot.clone();
+    let jira_client_for_sprint = app.state::<AppState>().jira_client.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(1900)).await;
+        loop {
+            let jira_sprint = collect_jira_sprint(&jira_client_for_sprint).await;
+            if let Ok(mut state) = snapshot_for_jira_sprint.write() {
+                state.jira_sprint = jira_sprint;
+            }
+            tokio::time::sleep(Duration::from_millis(60000)).await;
+        }
+    });
+
+    let snapshot_for_forge = app.state::<AppState>().snapshot.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(2900)).await;
+        loop {
+            let forge = collect_forge_overview().await;
+            if let Ok(mut state) = snapshot_for_forge.write() {
+                state.forge = forge;
+            }
+            tokio::time::sleep(Duration::from_millis(60000)).await;
+        }
+    });
+
+    let snapshot_for_pipelines = app.state::<AppState>().snapshot.clone();
+    let app_for_pipelines = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(3100)).await;
+        loop {
+            let pipelines = collect_pipeline_statuses().await;
+
+            let previous = snapshot_for_pipelines
+                .read()
+                .ok()
+                .and_then(|state| state.pipelines.clone().ok());
+            let failures = match (&previous, &pipelines) {
+                (Some(previous), Ok(current)) => diff_pipeline_statuses(previous, current),
+                _ => Vec::new(),
+            };
+
+            let notify_errors = if failures.is_empty() {
+                Vec::new()
+            } else {
+                notify_pipeline_failures(&app_for_pipelines, &failures)
+            };
+            let pipelines = pipelines.map(|mut statuses| {
+                for (failure, err) in notify_errors {
+                    if let Some(status) = statuses.iter_mut().find(|status| {
+                        status.provider == failure.provider
+                            && status.repository == failure.repository
+                            && status.branch == failure.branch
+                    }) {
+                        status.error.get_or_insert(err);
+                    }
+                }
+                statuses
+            });
+
+            if let Ok(mut state) = snapshot_for_pipelines.write() {
+                state.pipelines = pipelines;
+            }
+            tokio::time::sleep(Duration::from_millis(60000)).await;
+        }
+    });
+
+    let snapshot_for_git = app.state::<AppState>().snapshot.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(3300)).await;
         loop {
-            let jira = collect_jira_tickets().await;
-            if let Ok(mut state) = snapshot_for_jira.write() {
-                state.jira = jira;
+            // libgit2 is blocking and status scans can take a while on large checkouts
+            let git_repos = tauri::async_runtime::spawn_blocking(collect_git_repositories)
+                .await
+                .unwrap_or_else(|e| Err(format!("Git scan task failed: {}", e)));
+            if let Ok(mut state) = snapshot_for_git.write() {
+                state.git_repos = git_repos;
             }
             tokio::time::sleep(Duration::from_millis(30000)).await;
         }
     });
 
     let snapshot_for_health = app.state::<AppState>().snapshot.clone();
+    let health_history = app.state::<AppState>().health_history.clone();
+    let app_for_health = app.clone();
     tauri::async_runtime::spawn(async move {
         tokio::time::sleep(Duration::from_millis(2100)).await;
+        let mut tracker = HealthTracker::default();
+        let mut alerter = HealthAlerter::new();
         loop {
-            let health = collect_service_health().await;
+            let (transitions, mut errors) =
+                collect_service_health(&snapshot_for_health, &health_history, &mut tracker).await;
+            alerter.dispatch(&app_for_health, &transitions).await;
+            if let Ok(state) = snapshot_for_health.read() {
+                let names: Vec<String> = state.health.iter().map(|h| h.name.clone()).collect();
+                alerter.retain(&names);
+            }
+
+            errors.extend(AlertConfig::from_env().error);
+            errors.extend(alerter.errors());
             if let Ok(mut state) = snapshot_for_health.write() {
-                state.health = health;
+                state.health_errors = errors;
             }
             tokio::time::sleep(Duration::from_millis(20000)).await;
         }
@@ -818,7 +5843,50 @@ pub fn run() {
     tauri::Builder::default()
         .manage(app_state)
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .register_asynchronous_uri_scheme_protocol("artwork", |ctx, request, responder| {
+            let key = request.uri().path().trim_start_matches('/').to_string();
+            let artwork_cache = ctx.app_handle().state::<AppState>().artwork_cache.clone();
+
+            // Read the cached file off the webview thread
+            tauri::async_runtime::spawn_blocking(move || {
+                let response = match artwork_cache.read(&key) {
+                    Some(bytes) => tauri::http::Response::builder()
+                        .header(tauri::http::header::CONTENT_TYPE, artwork_mime_type(&bytes))
+                        .header(tauri::http::header::CACHE_CONTROL, "max-age=31536000, immutable")
+                        .body(bytes)
+                        .unwrap_or_default(),
+                    None => tauri::http::Response::builder()
+                        .status(tauri::http::StatusCode::NOT_FOUND)
+                        .body(Vec::new())
+                        .unwrap_or_default(),
+                };
+                responder.respond(response);
+            });
+        })
         .setup(|app| {
+            if let Ok(data_dir) = app.path().app_data_dir() {
+                let history = SpotifyHistory::load(data_dir.join("spotify_history.json"));
+                *app.state::<AppState>()
+                    .spotify_history
+                    .lock()
+                    .expect("failed to lock state") = history;
+
+                let health_history = HealthHistory::load(data_dir.join("health_history.jsonl"));
+                *app.state::<AppState>()
+                    .health_history
+                    .lock()
+                    .expect("failed to lock state") = health_history;
+            }
+
+            if let Ok(cache_dir) = app.path().app_cache_dir() {
+                let _ = app
+                    .state::<AppState>()
+                    .artwork_cache
+                    .dir
+                    .set(cache_dir.join("artwork"));
+            }
+
             start_background_pollers(app.handle());
 
             if let Some(window) = app.get_webview_window("main") {
@@ -845,10 +5913,33 @@ pub fn run() {
             get_ram_usage,
             get_docker_containers,
             get_spotify_track,
+            spotify_web_login,
+            spotify_play_pause,
+            spotify_next,
+            spotify_previous,
+            spotify_seek,
+            spotify_set_volume,
+            get_spotify_history,
+            get_spotify_history_error,
             get_cpu_usage,
+            get_jira_queries,
             get_jira_tickets,
+            get_jira_sprint,
+            get_jira_transitions,
+            transition_jira_ticket,
+            assign_jira_ticket_to_me,
+            add_jira_comment,
+            get_forge_overview,
+            get_pipeline_statuses,
+            get_git_repositories,
             get_service_health,
-            get_sentry_issues
+            get_health_errors,
+            get_service_uptime,
+            get_sentry_issues,
+            resolve_sentry_issue,
+            ignore_sentry_issue,
+            assign_sentry_issue,
+            bookmark_sentry_issue
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -859,13 +5950,24 @@ struct AppSnapshot {
     cpu: CpuUsage,
     docker: Result<Vec<DockerContainer>, String>,
     spotify: Result<SpotifyTrack, String>,
-    jira: Result<Vec<JiraTicket>, String>,
+    jira: HashMap<String, Result<JiraTicketList, String>>,
+    jira_sprint: Result<JiraSprint, String>,
+    forge: Result<ForgeOverview, String>,
+    pipelines: Result<Vec<PipelineStatus>, String>,
+    git_repos: Result<Vec<GitRepoStatus>, String>,
     health: Vec<ServiceHealth>,
-    sentry: Result<Vec<SentryIssue>, String>,
+    // Config, history and alert delivery failures, shown above the services
+    health_errors: Vec<String>,
+    sentry: Result<SentryIssueList, String>,
 }
 
 struct AppState {
     snapshot: Arc<RwLock<AppSnapshot>>,
+    spotify_web: Arc<SpotifyWebClient>,
+    spotify_history: Arc<Mutex<SpotifyHistory>>,
+    health_history: Arc<Mutex<HealthHistory>>,
+    artwork_cache: Arc<ArtworkCache>,
+    jira_client: Arc<JiraClient>,
 }
 
 impl AppState {
@@ -885,10 +5987,919 @@ impl AppState {
                 },
                 docker: Ok(Vec::new()),
                 spotify: Err("Loading Spotify data...".to_string()),
-                jira: Err("Loading Jira tickets...".to_string()),
+                jira: HashMap::new(),
+                jira_sprint: Err("Loading Jira sprint...".to_string()),
+                forge: Err("Loading pull requests...".to_string()),
+                pipelines: Err("Loading pipelines...".to_string()),
+                git_repos: Err("Loading git repositories...".to_string()),
                 health: Vec::new(),
+                health_errors: Vec::new(),
                 sentry: Err("Loading Sentry issues...".to_string()),
             })),
+            spotify_web: Arc::new(SpotifyWebClient {
+                client: reqwest::Client::builder()
+                    .timeout(Duration::from_secs(10))
+                    .build()
+                    .unwrap_or_else(|_| reqwest::Client::new()),
+                session: tokio::sync::Mutex::new(SpotifyWebSession::default()),
+            }),
+            spotify_history: Arc::new(Mutex::new(SpotifyHistory::default())),
+            health_history: Arc::new(Mutex::new(HealthHistory::default())),
+            artwork_cache: Arc::new(ArtworkCache {
+                dir: OnceLock::new(),
+                client: reqwest::Client::builder()
+                    .timeout(Duration::from_secs(10))
+                    .build()
+                    .unwrap_or_else(|_| reqwest::Client::new()),
+                palettes: Mutex::new(HashMap::new()),
+            }),
+            jira_client: Arc::new(JiraClient::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[derive(Clone, Debug)]
+    struct RecordedRequest {
+        method: String,
+        path: String,
+        // Lower-cased header names
+        headers: HashMap<String, String>,
+        body: String,
+    }
+
+    type MockResponder = dyn Fn(&RecordedRequest) -> (u16, String) + Send + Sync;
+
+    // Minimal HTTP/1.1 server on a random local port that records every request
+    async fn spawn_mock_server(
+        respond: impl Fn(&RecordedRequest) -> (u16, String) + Send + Sync + 'static,
+    ) -> (String, Arc<Mutex<Vec<RecordedRequest>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let respond: Arc<MockResponder> = Arc::new(respond);
+
+        let recorded_for_server = recorded.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let recorded = recorded_for_server.clone();
+                let respond = respond.clone();
+                tokio::spawn(serve_mock_connection(stream, recorded, respond));
+            }
+        });
+
+        (base_url, recorded)
+    }
+
+    async fn serve_mock_connection(
+        mut stream: tokio::net::TcpStream,
+        recorded: Arc<Mutex<Vec<RecordedRequest>>>,
+        respond: Arc<MockResponder>,
+    ) {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let header_end = loop {
+            let read = stream.read(&mut chunk).await.unwrap_or(0);
+            if read == 0 {
+                return;
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+            if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+        let mut lines = head.lines();
+        let mut request_line = lines.next().unwrap_or("").split_whitespace();
+        let method = request_line.next().unwrap_or("").to_string();
+        let path = request_line.next().unwrap_or("").to_string();
+        let headers: HashMap<String, String> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+            .collect();
+
+        let length: usize = headers
+            .get("content-length")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        while buffer.len() < header_end + length {
+            let read = stream.read(&mut chunk).await.unwrap_or(0);
+            if read == 0 {
+                break;
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+        let body_end = buffer.len().min(header_end + length);
+        let body = String::from_utf8_lossy(&buffer[header_end..body_end]).to_string();
+
+        let request = RecordedRequest {
+            method,
+            path,
+            headers,
+            body,
+        };
+        let (status, response_body) = respond(&request);
+        recorded.lock().unwrap().push(request);
+
+        let response = format!(
+            "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            response_body.len(),
+            response_body
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+        let _ = stream.shutdown().await;
+    }
+
+    fn form_params(body: &str) -> HashMap<String, String> {
+        parse_query_params(&format!("?{}", body))
+    }
+
+    #[tokio::test]
+    async fn spotify_token_exchange_and_refresh_against_mock_accounts_server() {
+        let (base_url, recorded) = spawn_mock_server(|request| {
+            let form = form_params(&request.body);
+            match form.get("grant_type").map(String::as_str) {
+                Some("authorization_code") => (
+                    200,
+                    r#"{"access_token":"access-1","expires_in":3600,"refresh_token":"refresh-1"}"#
+                        .to_string(),
+                ),
+                Some("refresh_token") => (
+                    200,
+                    r#"{"access_token":"access-2","expires_in":3600,"refresh_token":"refresh-2"}"#
+                        .to_string(),
+                ),
+                _ => (400, r#"{"error":"unsupported_grant_type"}"#.to_string()),
+            }
+        })
+        .await;
+
+        let config = SpotifyWebConfig {
+            client_id: "client-123".to_string(),
+            redirect_port: 8898,
+            accounts_base_url: base_url.clone(),
+            api_base_url: base_url,
+        };
+        let web = SpotifyWebClient {
+            client: reqwest::Client::new(),
+            session: tokio::sync::Mutex::new(SpotifyWebSession::default()),
+        };
+
+        let (access, refresh) = exchange_spotify_code(&web, &config, "auth-code", "verifier-xyz")
+            .await
+            .unwrap();
+        assert_eq!(access, "access-1");
+        assert_eq!(refresh.as_deref(), Some("refresh-1"));
+        assert_eq!(web.session.lock().await.access_token.as_deref(), Some("access-1"));
+
+        let mut session = SpotifyWebSession::default();
+        let (access, refresh) = refresh_spotify_token(&web, &config, &mut session, "refresh-1")
+            .await
+            .unwrap();
+        assert_eq!(access, "access-2");
+        assert_eq!(refresh.as_deref(), Some("refresh-2"));
+        assert!(session.expires_at.is_some());
+
+        let requests = recorded.lock().unwrap().clone();
+        assert_eq!(requests.len(), 2);
+        assert!(requests.iter().all(|r| r.method == "POST" && r.path == "/api/token"));
+        assert!(requests[0].headers["content-type"].starts_with("application/x-www-form-urlencoded"));
+
+        let exchange = form_params(&requests[0].body);
+        assert_eq!(exchange["code"], "auth-code");
+        assert_eq!(exchange["code_verifier"], "verifier-xyz");
+        assert_eq!(exchange["client_id"], "client-123");
+        assert_eq!(exchange["redirect_uri"], "http://127.0.0.1:8898/callback");
+
+        let refresh = form_params(&requests[1].body);
+        assert_eq!(refresh["refresh_token"], "refresh-1");
+        assert_eq!(refresh["client_id"], "client-123");
+    }
+
+    #[tokio::test]
+    async fn spotify_web_playback_is_parsed_from_a_mock_player() {
+        // The access token picks the scenario so the mock needs no state
+        let (base_url, recorded) = spawn_mock_server(|request| {
+            match request.headers.get("authorization").map(String::as_str) {
+                Some("Bearer track") => (
+                    200,
+                    serde_json::json!({
+                        "is_playing": true,
+                        "progress_ms": 42_000,
+                        "shuffle_state": true,
+                        "repeat_state": "context",
+                        "currently_playing_type": "track",
+                        "item": {
+                            "name": "Song",
+                            "duration_ms": 240_000,
+                            "artists": [{ "name": "First" }, { "name": "Second" }],
+                            "album": { "name": "Album", "images": [{ "url": "https://i.scdn.co/image/cover" }] }
+                        }
+                    })
+                    .to_string(),
+                ),
+                Some("Bearer episode") => (
+                    200,
+                    r#"{"is_playing": true, "currently_playing_type": "episode", "item": null}"#.to_string(),
+                ),
+                Some("Bearer idle") => (204, String::new()),
+                _ => (401, r#"{"error":{"status":401,"message":"The access token expired"}}"#.to_string()),
+            }
+        })
+        .await;
+        let config = SpotifyWebConfig {
+            client_id: "client-123".to_string(),
+            redirect_port: 8898,
+            accounts_base_url: base_url.clone(),
+            api_base_url: base_url,
+        };
+        let web = SpotifyWebClient {
+            client: reqwest::Client::new(),
+            session: tokio::sync::Mutex::new(SpotifyWebSession::default()),
+        };
+        let use_token = |token: &str| {
+            let mut session = web.session.try_lock().unwrap();
+            session.access_token = Some(token.to_string());
+            session.expires_at = Some(std::time::Instant::now() + Duration::from_secs(3600));
+        };
+
+        use_token("track");
+        let track = collect_spotify_web_track(&web, &config).await.unwrap();
+        assert_eq!(track.track_name, "Song");
+        assert_eq!(track.artist, "First, Second");
+        assert_eq!(track.album, "Album");
+        assert_eq!(track.artwork_url, "https://i.scdn.co/image/cover");
+        assert!(track.is_playing);
+        assert_eq!(track.position_ms, 42_000);
+        assert_eq!(track.duration_ms, 240_000);
+        assert!(track.shuffle);
+        assert_eq!(track.repeat, "context");
+
+        use_token("episode");
+        let err = collect_spotify_web_track(&web, &config).await.err().unwrap();
+        assert_eq!(err, "No track playing");
+
+        use_token("idle");
+        let err = collect_spotify_web_track(&web, &config).await.err().unwrap();
+        assert_eq!(err, "No track playing");
+
+        // A rejected token is dropped so the next poll refreshes it
+        use_token("expired");
+        let err = collect_spotify_web_track(&web, &config).await.err().unwrap();
+        assert_eq!(err, "Spotify access token expired");
+        assert!(web.session.lock().await.access_token.is_none());
+
+        let requests = recorded.lock().unwrap();
+        assert_eq!(requests.len(), 4);
+        assert!(requests.iter().all(|r| r.method == "GET" && r.path == "/v1/me/player"));
+    }
+
+    fn playing_track(position_ms: u64) -> Result<SpotifyTrack, String> {
+        Ok(SpotifyTrack {
+            track_name: "Song".to_string(),
+            artist: "Artist".to_string(),
+            album: "Album".to_string(),
+            artwork_url: String::new(),
+            is_playing: true,
+            position_ms,
+            duration_ms: 240_000,
+            shuffle: false,
+            repeat: "off".to_string(),
+            artwork_cached_url: None,
+            palette: None,
+        })
+    }
+
+    #[test]
+    fn spotify_history_survives_transient_errors() {
+        let mut history = SpotifyHistory::default();
+        assert!(!history.observe(&playing_track(0)));
+        history.current.as_mut().unwrap().track.listened_ms = 60_000;
+
+        assert!(!history.observe(&Err("osascript failed".to_string())));
+        assert!(!history.observe(&playing_track(63_000)));
+
+        assert!(history.entries.is_empty());
+        assert_eq!(history.current.as_ref().unwrap().track.listened_ms, 60_000);
+    }
+
+    #[test]
+    fn spotify_history_records_replays_of_the_same_track() {
+        let mut history = SpotifyHistory::default();
+        history.observe(&playing_track(0));
+        history.current.as_mut().unwrap().track.listened_ms = 60_000;
+        history.observe(&playing_track(200_000));
+
+        // Back at the start of the same track
+        assert!(history.observe(&playing_track(2_000)));
+        assert_eq!(history.entries.len(), 1);
+        assert!(history.current.is_some());
+    }
+
+    #[test]
+    fn spotify_history_saves_skip_stale_snapshots() {
+        let dir = env::temp_dir().join(format!("dashboard-spotify-{}", random_token(8)));
+        let path = dir.join("spotify_history.json");
+        let history = Mutex::new(SpotifyHistory::load(path.clone()));
+
+        // The newer snapshot finishes first, the older one must not replace it
+        save_spotify_history(&history, &path, "[\"newer\"]", 2);
+        save_spotify_history(&history, &path, "[\"older\"]", 1);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[\"newer\"]");
+
+        // Writing onto a directory fails, a stale success may not clear that error
+        save_spotify_history(&history, &dir, "[]", 3);
+        assert!(history.lock().unwrap().save_error.is_some());
+        save_spotify_history(&history, &path, "[\"older\"]", 2);
+        assert!(history.lock().unwrap().save_error.is_some());
+
+        save_spotify_history(&history, &path, "[]", 4);
+        assert!(history.lock().unwrap().save_error.is_none());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[]");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn spotify_token_errors_are_reported() {
+        let (base_url, _) =
+            spawn_mock_server(|_| (400, r#"{"error":"invalid_grant"}"#.to_string())).await;
+        let config = SpotifyWebConfig {
+            client_id: "client-123".to_string(),
+            redirect_port: 8898,
+            accounts_base_url: base_url.clone(),
+            api_base_url: base_url,
+        };
+        let web = SpotifyWebClient {
+            client: reqwest::Client::new(),
+            session: tokio::sync::Mutex::new(SpotifyWebSession::default()),
+        };
+
+        let mut session = SpotifyWebSession::default();
+        let err = refresh_spotify_token(&web, &config, &mut session, "stale")
+            .await
+            .unwrap_err();
+        assert!(err.contains("400"), "{}", err);
+        assert!(session.access_token.is_none());
+    }
+
+    #[test]
+    fn legacy_sprint_names_keep_their_commas() {
+        let raw = "com.atlassian.greenhopper.service.sprint.Sprint@1f[id=3,rapidViewId=1,\
+                   state=ACTIVE,name=Sprint 7, part 2,startDate=2024-01-01T00:00:00.000Z,\
+                   endDate=<null>,completeDate=<null>,sequence=3]";
+
+        assert_eq!(
+            legacy_sprint_attribute(raw, "name").as_deref(),
+            Some("Sprint 7, part 2")
+        );
+        assert_eq!(legacy_sprint_attribute(raw, "state").as_deref(), Some("ACTIVE"));
+        assert_eq!(legacy_sprint_attribute(raw, "id").as_deref(), Some("3"));
+        assert_eq!(legacy_sprint_attribute(raw, "sequence").as_deref(), Some("3"));
+        assert_eq!(legacy_sprint_attribute(raw, "goal"), None);
+    }
+
+    fn jira_ticket(key: &str, status: &str) -> JiraTicket {
+        JiraTicket {
+            key: key.to_string(),
+            summary: format!("Summary of {}", key),
+            status: status.to_string(),
+            assignee: "Unassigned".to_string(),
+            url: format!("https://jira.example.com/browse/{}", key),
+            priority: None,
+            priority_icon_url: None,
+            issue_type: None,
+            status_category: "new".to_string(),
+            status_color: String::new(),
+            sprint: None,
+            due_date: None,
+            labels: Vec::new(),
+            reporter: None,
+            updated: String::new(),
+            extra_fields: HashMap::new(),
+            is_mine: false,
+        }
+    }
+
+    #[test]
+    fn jira_diff_ignores_tickets_returning_to_the_first_page() {
+        let query = JiraQuery {
+            id: "mine".to_string(),
+            name: "My Tickets".to_string(),
+            jql: "assignee = currentUser()".to_string(),
+            notify: true,
+        };
+        let list = |tickets: Vec<JiraTicket>| JiraTicketList {
+            total: 10,
+            tickets,
+            errors: Vec::new(),
+        };
+        let seen: HashSet<String> = ["A-1", "A-2", "A-3"].map(str::to_string).into();
+
+        // A-3 was pushed off the limited page last poll and is back now
+        let previous = list(vec![jira_ticket("A-1", "To Do"), jira_ticket("A-2", "To Do")]);
+        let current = list(vec![
+            jira_ticket("A-3", "To Do"),
+            jira_ticket("A-1", "In Progress"),
+            jira_ticket("A-4", "To Do"),
+        ]);
+
+        let changes = diff_jira_tickets(&query, &previous, &current, &seen);
+        let summary: Vec<(&str, &str)> = changes
+            .iter()
+            .map(|change| (change.key.as_str(), change.kind.as_str()))
+            .collect();
+        assert_eq!(summary, vec![("A-1", "status_changed"), ("A-4", "added")]);
+        assert_eq!(changes[0].from_status.as_deref(), Some("To Do"));
+    }
+
+    fn fixture(name: &str) -> serde_json::Value {
+        let raw = match name {
+            "github_overview" => include_str!("../tests/fixtures/github_overview.json"),
+            "gitlab_merge_requests" => include_str!("../tests/fixtures/gitlab_merge_requests.json"),
+            "gitlab_issues" => include_str!("../tests/fixtures/gitlab_issues.json"),
+            "gitlab_pipelines" => include_str!("../tests/fixtures/gitlab_pipelines.json"),
+            _ => panic!("unknown fixture {}", name),
+        };
+        serde_json::from_str(raw).expect("fixture is valid JSON")
+    }
+
+    #[test]
+    fn github_overview_fixture_is_parsed() {
+        let mut overview = ForgeOverview::default();
+        parse_github_overview(&fixture("github_overview"), &mut overview).unwrap();
+
+        // The empty node does not match the pull request fragment and is skipped
+        assert_eq!(overview.review_requested.len(), 1);
+        let review = &overview.review_requested[0];
+        assert_eq!(review.provider, "github");
+        assert_eq!(review.repository, "acme/dashboard");
+        assert_eq!(review.number, 482);
+        assert_eq!(review.author, "octocat");
+        assert_eq!(review.updated_at, "2024-05-14T09:12:44Z");
+        assert_eq!(review.ci_status.as_deref(), Some("failure"));
+
+        let draft = &overview.authored[0];
+        assert!(draft.is_draft);
+        assert_eq!(draft.author, "ghost");
+        assert_eq!(draft.ci_status, None);
+        assert_eq!(overview.authored[1].ci_status.as_deref(), Some("pending"));
+
+        let issue = &overview.assigned_issues[0];
+        assert_eq!(issue.number, 455);
+        assert!(!issue.is_draft);
+        assert_eq!(issue.url, "https://github.com/acme/dashboard/issues/455");
+    }
+
+    #[test]
+    fn github_overview_errors_are_reported() {
+        let json = serde_json::json!({
+            "data": null,
+            "errors": [{ "type": "INSUFFICIENT_SCOPES", "message": "Your token has not been granted the required scopes" }]
+        });
+        let mut overview = ForgeOverview::default();
+
+        let err = parse_github_overview(&json, &mut overview).unwrap_err();
+        assert!(err.contains("required scopes"), "{}", err);
+        assert!(overview.review_requested.is_empty());
+    }
+
+    #[test]
+    fn gitlab_fixtures_are_parsed() {
+        let merge_requests: Vec<ForgeItem> = fixture("gitlab_merge_requests")
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| parse_gitlab_item(item, '!'))
+            .collect();
+
+        // Nested groups stay part of the repository path
+        assert_eq!(merge_requests[0].repository, "acme/platform/backend");
+        assert_eq!(merge_requests[0].number, 12);
+        assert_eq!(merge_requests[0].author, "jdoe");
+        assert_eq!(merge_requests[0].updated_at, "2024-05-14T10:20:30.000Z");
+        assert!(merge_requests[0].is_draft);
+        // Older GitLab versions only send work_in_progress
+        assert_eq!(merge_requests[1].repository, "acme/web");
+        assert!(!merge_requests[1].is_draft);
+
+        let issue = parse_gitlab_item(&fixture("gitlab_issues")[0], '#');
+        assert_eq!(issue.provider, "gitlab");
+        assert_eq!(issue.repository, "acme/platform/backend");
+        assert_eq!(issue.number, 34);
+        assert_eq!(issue.url, "https://gitlab.com/acme/platform/backend/-/issues/34");
+
+        assert_eq!(
+            latest_gitlab_pipeline_status(&fixture("gitlab_pipelines")).as_deref(),
+            Some("pending")
+        );
+        assert_eq!(latest_gitlab_pipeline_status(&serde_json::json!([])), None);
+    }
+
+    #[test]
+    fn health_checks_with_flattened_assertions_are_parsed() {
+        let services = parse_health_check_configs(
+            r#"[
+                {
+                    "name": "API",
+                    "url": "https://api.example.com/health",
+                    "expected_status": [200, 204],
+                    "json_path": "checks.database.status",
+                    "json_equals": "ok",
+                    "max_latency_ms": 800,
+                    "failure_threshold": 3,
+                    "failure_window": 5
+                },
+                { "name": "Redis", "kind": "tcp", "target": "redis.internal:6379" }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(services.len(), 2);
+        let api = &services[0];
+        assert_eq!(api.assertions.max_latency_ms, Some(800));
+        assert_eq!(api.assertions.expected_status, vec![200, 204]);
+        assert_eq!(api.assertions.json_equals, Some(serde_json::json!("ok")));
+        assert_eq!(api.policy.failure_threshold, Some(3));
+        assert_eq!(api.policy.failure_window, Some(5));
+        assert_eq!(services[1].kind, "tcp");
+        assert_eq!(services[1].url, "redis.internal:6379");
+        assert_eq!(services[1].assertions.max_latency_ms, None);
+
+        let err = parse_health_check_configs(r#"[{"name": "API"}]"#).err().unwrap();
+        assert!(err.starts_with("Failed to parse HEALTH_CHECKS"), "{}", err);
+    }
+
+    #[test]
+    fn probe_targets_accept_bracketed_ipv6() {
+        let target = |raw: &str| probe_target(raw, 0);
+
+        assert_eq!(target("redis.internal:6379"), Ok(("redis.internal".to_string(), 6379)));
+        assert_eq!(target("example.com"), Ok(("example.com".to_string(), 0)));
+        assert_eq!(target("[::1]:6379"), Ok(("::1".to_string(), 6379)));
+        assert_eq!(target("[fe80::1]"), Ok(("fe80::1".to_string(), 0)));
+        assert_eq!(probe_target("tls://[::1]:8443", 443), Ok(("::1".to_string(), 8443)));
+        assert_eq!(probe_target("https://[::1]/", 0), Ok(("::1".to_string(), 443)));
+
+        // Without brackets the address is taken as a whole and gets no port
+        assert_eq!(target("::1"), Ok(("::1".to_string(), 0)));
+        assert!(target("fe80::zz:6379").is_err());
+        assert!(target("[::1]6379").is_err());
+        assert!(target("[::1:6379").is_err());
+    }
+
+    #[test]
+    fn health_history_appends_outside_the_lock_and_buckets_samples() {
+        let dir = env::temp_dir().join(format!("dashboard-health-{}", random_token(8)));
+        let path = dir.join("health_history.jsonl");
+        let mut history = HealthHistory::load(path.clone());
+
+        let service = &parse_health_check_configs(r#"[{"name": "API", "url": "http://localhost"}]"#)
+            .unwrap()[0];
+        let now = unix_time_ms();
+        for (minutes_ago, state) in [(50, "up"), (40, "down"), (20, "degraded"), (5, "up")] {
+            let mut health = ServiceHealth::new(service, now - minutes_ago * 60 * 1000);
+            health.state = state.to_string();
+            health.latency_ms = Some(100);
+            history.record(&health, state);
+        }
+
+        // Recording alone does not touch the file
+        assert!(!path.exists() || std::fs::read_to_string(&path).unwrap().is_empty());
+        let write = history.pending_write().expect("recorded samples are pending");
+        assert!(matches!(write, HealthHistoryWrite::Append(..)));
+        write.apply().unwrap();
+        assert!(history.pending_write().is_none());
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 4);
+
+        // 1 hour in 4 buckets of 15 minutes
+        let uptime = history.uptime("API", 1, 4);
+        let states: Vec<Option<&str>> = uptime.buckets.iter().map(|b| b.state.as_deref()).collect();
+        assert_eq!(states, vec![Some("up"), Some("down"), Some("degraded"), Some("up")]);
+        assert_eq!(uptime.buckets[1].uptime_percent, Some(0.0));
+        assert_eq!(uptime.buckets[2].uptime_percent, Some(100.0));
+        assert_eq!(uptime.uptime_24h, Some(75.0));
+
+        // A compaction replaces the file with what is still in memory
+        let reloaded = HealthHistory::load(path.clone());
+        assert_eq!(reloaded.samples["API"].len(), 4);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 4);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn health_tracker_smooths_single_failures_and_flapping() {
+        let service = &parse_health_check_configs(r#"[{"name": "API", "url": "http://localhost"}]"#)
+            .unwrap()[0];
+        let check = |down: bool| {
+            let mut health = ServiceHealth::new(service, unix_time_ms());
+            if down {
+                health.apply_failures(vec!["Status 503".to_string()], Vec::new());
+            } else {
+                health.apply_failures(Vec::new(), Vec::new());
+            }
+            health
+        };
+
+        // A single failure below the default 2 of 3 threshold stays up without an error
+        let mut tracker = HealthTracker::default();
+        let policy = HealthPolicy::default();
+        tracker.apply(&policy, &mut check(false));
+        let mut health = check(true);
+        assert_eq!(tracker.apply(&policy, &mut health), "up");
+        assert_eq!(health.state, "up");
+        assert!(health.is_up);
+        assert_eq!(health.error, None);
+        assert!(health.failed_assertions.is_empty());
+        assert_eq!(health.recent_failures, 1);
+
+        // Flapping keeps reporting whether the service is up underneath
+        let mut tracker = HealthTracker::default();
+        let policy = HealthPolicy {
+            failure_threshold: Some(1),
+            flap_threshold: Some(2),
+            flap_window: Some(3),
+            ..Default::default()
+        };
+        tracker.apply(&policy, &mut check(false));
+        tracker.apply(&policy, &mut check(true));
+        let mut health = check(false);
+        assert_eq!(tracker.apply(&policy, &mut health), "up");
+        assert_eq!(health.state, "flapping");
+        assert!(health.is_up);
+
+        let mut health = check(true);
+        assert_eq!(tracker.apply(&policy, &mut health), "down");
+        assert_eq!(health.state, "flapping");
+        assert!(!health.is_up);
+        assert_eq!(health.error.as_deref(), Some("Status 503"));
+
+        // A service removed from the config starts over when its name comes back
+        tracker.retain(&[]);
+        let mut health = check(true);
+        assert_eq!(tracker.apply(&policy, &mut health), "down");
+        assert_eq!(health.state, "down");
+        assert_eq!(health.recent_failures, 1);
+    }
+
+    fn health_transition(kind: &str, from_state: &str, to_state: &str) -> HealthTransition {
+        HealthTransition {
+            name: "API".to_string(),
+            url: "https://api.example.com/health".to_string(),
+            kind: kind.to_string(),
+            from_state: from_state.to_string(),
+            to_state: to_state.to_string(),
+            error: (to_state == "down").then(|| "Status 503".to_string()),
+            changed_at_ms: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn health_transitions_and_cooldowns() {
+        assert_eq!(health_transition_kind("up", "down"), Some("down"));
+        assert_eq!(health_transition_kind("down", "flapping"), Some("flapping"));
+        assert_eq!(health_transition_kind("down", "up"), Some("recovered"));
+        assert_eq!(health_transition_kind("down", "degraded"), Some("recovered"));
+        assert_eq!(health_transition_kind("flapping", "up"), Some("recovered"));
+        assert_eq!(health_transition_kind("flapping", "degraded"), Some("recovered"));
+        assert_eq!(health_transition_kind("flapping", "down"), Some("down"));
+        assert_eq!(health_transition_kind("up", "degraded"), None);
+        assert_eq!(health_transition_kind("flapping", "flapping"), None);
+
+        let cooldown = Duration::from_secs(300);
+        let mut alerter = HealthAlerter::new();
+        let down = health_transition("down", "up", "down");
+        let recovered = health_transition("recovered", "down", "up");
+        assert!(alerter.cooldown_allows(&down, cooldown));
+
+        // The recovery closes an outage alert that went out, even within the cooldown
+        alerter
+            .last_sent
+            .insert("API".to_string(), (std::time::Instant::now(), "down".to_string()));
+        assert!(alerter.cooldown_allows(&recovered, cooldown));
+        assert!(!alerter.cooldown_allows(&down, cooldown));
+
+        // The next outage within the cooldown is suppressed, and so is its recovery
+        alerter
+            .last_sent
+            .insert("API".to_string(), (std::time::Instant::now(), "recovered".to_string()));
+        assert!(!alerter.cooldown_allows(&down, cooldown));
+        assert!(!alerter.cooldown_allows(&recovered, cooldown));
+        assert!(alerter.cooldown_allows(&down, Duration::ZERO));
+
+        alerter.retain(&[]);
+        assert!(alerter.cooldown_allows(&down, cooldown));
+    }
+
+    #[tokio::test]
+    async fn health_alert_webhooks_post_to_a_local_sink() {
+        let (base_url, recorded) = spawn_mock_server(|request| match request.path.as_str() {
+            "/broken" => (500, "nope".to_string()),
+            _ => (200, "ok".to_string()),
+        })
+        .await;
+        let client = reqwest::Client::new();
+        let transition = health_transition("down", "up", "down");
+        let (title, body) = health_alert_message(&transition);
+
+        let generic: AlertWebhook =
+            serde_json::from_value(serde_json::json!({ "url": format!("{}/generic", base_url) }))
+                .unwrap();
+        let slack: AlertWebhook = serde_json::from_value(serde_json::json!({
+            "url": format!("{}/slack", base_url),
+            "format": "slack"
+        }))
+        .unwrap();
+
+        for webhook in [&generic, &slack] {
+            let payload = alert_webhook_payload(webhook, &transition, &title, &body);
+            post_alert_webhook(&client, &webhook.url, &payload).await.unwrap();
         }
+        let err = post_alert_webhook(&client, &format!("{}/broken", base_url), &serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(err.contains("returned 500"), "{}", err);
+
+        let requests = recorded.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/generic");
+        assert_eq!(
+            requests[0].headers.get("content-type").map(String::as_str),
+            Some("application/json")
+        );
+        let generic_body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(generic_body["name"], "API");
+        assert_eq!(generic_body["kind"], "down");
+        assert_eq!(generic_body["from_state"], "up");
+        assert_eq!(generic_body["to_state"], "down");
+        assert_eq!(generic_body["error"], "Status 503");
+
+        assert_eq!(requests[1].path, "/slack");
+        let slack_body: serde_json::Value = serde_json::from_str(&requests[1].body).unwrap();
+        assert_eq!(slack_body, serde_json::json!({ "text": "*API is down*\nStatus 503" }));
+    }
+
+    fn sentry_issue(id: &str, last_seen: &str, events: u64) -> SentryIssue {
+        parse_sentry_issue(
+            0,
+            "acme",
+            &serde_json::json!({
+                "id": id,
+                "title": id,
+                "lastSeen": last_seen,
+                "firstSeen": last_seen,
+                "count": events.to_string(),
+            }),
+        )
+    }
+
+    #[test]
+    fn sentry_sources_merge_by_the_configured_sort() {
+        let ids = |issues: Vec<SentryIssue>| issues.into_iter().map(|i| i.id).collect::<Vec<_>>();
+        let first = vec![
+            sentry_issue("a1", "2026-01-01T10:00:00Z", 5),
+            sentry_issue("a2", "2026-01-03T10:00:00Z", 1),
+        ];
+        let second = vec![
+            sentry_issue("b1", "2026-01-02T10:00:00Z", 9),
+            sentry_issue("b2", "2026-01-04T10:00:00Z", 3),
+        ];
+
+        let by_freq = merge_sentry_issues(vec![
+            ("freq".to_string(), first.clone()),
+            ("freq".to_string(), second.clone()),
+        ]);
+        assert_eq!(ids(by_freq), ["b1", "a1", "b2", "a2"]);
+
+        let by_date = merge_sentry_issues(vec![
+            ("date".to_string(), first.clone()),
+            ("date".to_string(), second.clone()),
+        ]);
+        assert_eq!(ids(by_date), ["b2", "a2", "b1", "a1"]);
+
+        // Sentry-scored sorts keep each source's order and interleave them
+        let by_priority = merge_sentry_issues(vec![
+            ("priority".to_string(), first),
+            ("priority".to_string(), second),
+        ]);
+        assert_eq!(ids(by_priority), ["a1", "b1", "a2", "b2"]);
+    }
+
+    #[test]
+    fn sentry_link_header_pagination() {
+        let link = "<https://sentry.io/api/0/organizations/acme/projects/?&cursor=100:-1:1>; rel=\"previous\"; results=\"false\"; cursor=\"100:-1:1\", <https://sentry.io/api/0/organizations/acme/projects/?&cursor=100:1:0>; rel=\"next\"; results=\"true\"; cursor=\"100:1:0\"";
+        assert_eq!(
+            sentry_next_page_url(link).as_deref(),
+            Some("https://sentry.io/api/0/organizations/acme/projects/?&cursor=100:1:0")
+        );
+
+        let last = "<https://sentry.io/api/0/organizations/acme/projects/?&cursor=100:2:0>; rel=\"next\"; results=\"false\"; cursor=\"100:2:0\"";
+        assert_eq!(sentry_next_page_url(last), None);
+    }
+
+    #[tokio::test]
+    async fn sentry_sources_use_their_own_base_url_and_token() {
+        let (alpha_url, alpha_requests) = spawn_mock_server(|request| {
+            if request.path.contains("/projects/") {
+                (200, r#"[{"id": "11", "slug": "api"}, {"id": "7", "slug": "web"}]"#.to_string())
+            } else {
+                let issue = r#"[{"id": "1", "title": "Alpha", "lastSeen": "2026-01-02T10:00:00Z", "firstSeen": "2026-01-01T10:00:00Z", "count": "4"}]"#;
+                (200, issue.to_string())
+            }
+        })
+        .await;
+        let (beta_url, beta_requests) = spawn_mock_server(|_| {
+            let issue = r#"[{"id": "2", "title": "Beta", "lastSeen": "2026-01-03T10:00:00Z", "firstSeen": "2026-01-01T10:00:00Z", "count": "9"}]"#;
+            (200, issue.to_string())
+        })
+        .await;
+        env::set_var("DASHBOARD_TEST_SENTRY_ALPHA_TOKEN", "alpha-token");
+        env::set_var("DASHBOARD_TEST_SENTRY_BETA_TOKEN", "beta-token");
+
+        let sources: Vec<SentrySource> = serde_json::from_value(serde_json::json!([
+            {
+                "organization": "alpha",
+                "base_url": format!("{}/", alpha_url),
+                "auth_token": { "env": "DASHBOARD_TEST_SENTRY_ALPHA_TOKEN" },
+                "projects": ["web"]
+            },
+            {
+                "organization": "beta",
+                "base_url": beta_url,
+                "auth_token": { "env": "DASHBOARD_TEST_SENTRY_BETA_TOKEN" },
+                "projects": ["42"],
+                "environments": ["production"]
+            }
+        ]))
+        .unwrap();
+
+        let list = collect_sentry_issue_list(&sources).await.unwrap();
+        assert!(list.errors.is_empty(), "{:?}", list.errors);
+        let issues: Vec<(&str, &str)> = list
+            .issues
+            .iter()
+            .map(|issue| (issue.organization.as_str(), issue.id.as_str()))
+            .collect();
+        assert_eq!(issues, [("beta", "2"), ("alpha", "1")]);
+
+        let alpha = alpha_requests.lock().unwrap();
+        assert_eq!(alpha.len(), 2);
+        assert_eq!(alpha[0].path, "/api/0/organizations/alpha/projects/");
+        assert!(alpha[1].path.starts_with("/api/0/organizations/alpha/issues/?"), "{}", alpha[1].path);
+        assert!(alpha[1].path.contains("&project=7"), "{}", alpha[1].path);
+        for request in alpha.iter() {
+            assert_eq!(request.method, "GET");
+            assert_eq!(
+                request.headers.get("authorization").map(String::as_str),
+                Some("Bearer alpha-token")
+            );
+        }
+
+        let beta = beta_requests.lock().unwrap();
+        assert_eq!(beta.len(), 1);
+        assert!(beta[0].path.starts_with("/api/0/organizations/beta/issues/?"), "{}", beta[0].path);
+        assert!(beta[0].path.contains("&project=42&environment=production"), "{}", beta[0].path);
+        assert_eq!(
+            beta[0].headers.get("authorization").map(String::as_str),
+            Some("Bearer beta-token")
+        );
+    }
+
+    #[test]
+    fn gitlab_canceled_pipelines_count_as_failures() {
+        assert_eq!(normalize_ci_status("canceled").as_deref(), Some("failure"));
+    }
+
+    #[test]
+    fn github_cancelled_runs_count_as_failures() {
+        assert_eq!(normalize_ci_status("cancelled").as_deref(), Some("failure"));
+        assert_eq!(normalize_ci_status("skipped"), None);
+    }
+
+    #[test]
+    fn github_run_duration_ends_with_the_last_job() {
+        let jobs = serde_json::json!({
+            "jobs": [
+                { "name": "lint", "conclusion": "success", "completed_at": "2024-05-14T10:02:00Z" },
+                { "name": "test", "conclusion": "failure", "completed_at": "2024-05-14T10:07:30Z" },
+                { "name": "deploy", "conclusion": "skipped", "completed_at": null }
+            ]
+        });
+        let (completed_at, failed_jobs) = summarize_github_jobs(&jobs);
+        assert_eq!(failed_jobs, ["test"]);
+        assert_eq!(
+            duration_between(Some("2024-05-14T10:00:00Z"), completed_at.as_deref()),
+            Some(450)
+        );
+
+        assert_eq!(summarize_github_jobs(&serde_json::json!({})), (None, Vec::new()));
     }
 }